@@ -1,5 +1,4 @@
 use crate::common::{Error, Result};
-use alloc::format;
 use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
 use num_derive::{FromPrimitive, ToPrimitive};
 use scale::{Decode, Encode};
@@ -41,7 +40,7 @@ impl core::convert::TryFrom<u8> for File {
 
     fn try_from(value: u8) -> Result<Self> {
         num::FromPrimitive::from_u8(value)
-            .ok_or_else(|| Error::InvalidArgument(format!("Invalid File index: {}", value)))
+            .ok_or(Error::InvalidFile)
     }
 }
 
@@ -77,7 +76,7 @@ impl core::convert::TryFrom<char> for File {
             'f' => Ok(F),
             'g' => Ok(G),
             'h' => Ok(H),
-            c => Err(Error::InvalidArgument(format!("Invalid File char: {}", c))),
+            _ => Err(Error::InvalidFile),
         }
     }
 }
@@ -9,8 +9,8 @@ use scale::{Decode, Encode};
 
 type MovEncoded = u16;
 
-#[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
-#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, SpreadLayout, PackedLayout)]
+#[cfg_attr(feature = "std", derive(Debug, scale_info::TypeInfo, StorageLayout))]
 pub struct Mov {
     from: Square,
     to: Square,
@@ -46,16 +46,14 @@ impl core::convert::TryFrom<&str> for Mov {
     type Error = Error;
 
     fn try_from(value: &str) -> Result<Self> {
-        let error_invalid_mov = || Error::InvalidArgument(format!("Invalid move: {}", value));
-
         let mut chars = value.chars();
 
-        let file: File = chars.next().ok_or_else(error_invalid_mov)?.try_into()?;
-        let rank: Rank = chars.next().ok_or_else(error_invalid_mov)?.try_into()?;
+        let file: File = chars.next().ok_or(Error::InvalidMove)?.try_into()?;
+        let rank: Rank = chars.next().ok_or(Error::InvalidMove)?.try_into()?;
         let from = Square::new(file, rank);
 
-        let file: File = chars.next().ok_or_else(error_invalid_mov)?.try_into()?;
-        let rank: Rank = chars.next().ok_or_else(error_invalid_mov)?.try_into()?;
+        let file: File = chars.next().ok_or(Error::InvalidMove)?.try_into()?;
+        let rank: Rank = chars.next().ok_or(Error::InvalidMove)?.try_into()?;
         let to = Square::new(file, rank);
 
         let promotion: Option<Piece> = match chars.next() {
@@ -63,6 +61,10 @@ impl core::convert::TryFrom<&str> for Mov {
             None => None,
         };
 
+        if chars.next().is_some() {
+            return Err(Error::InvalidMove);
+        }
+
         Ok(Self {
             from,
             to,
@@ -1,5 +1,4 @@
 use crate::common::{Error, Result};
-use alloc::format;
 use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
 use num_derive::{FromPrimitive, ToPrimitive};
 use scale::{Decode, Encode};
@@ -40,7 +39,7 @@ impl core::convert::TryFrom<u8> for Piece {
 
     fn try_from(value: u8) -> Result<Self> {
         num::FromPrimitive::from_u8(value)
-            .ok_or_else(|| Error::InvalidArgument(format!("Invalid Piece index: {}", value)))
+            .ok_or(Error::InvalidPiece)
     }
 }
 
@@ -72,7 +71,7 @@ impl core::convert::TryFrom<char> for Piece {
             'b' => Ok(Bishop),
             'q' => Ok(Queen),
             'k' => Ok(King),
-            c => Err(Error::InvalidArgument(format!("Invalid Piece char: {}", c))),
+            _ => Err(Error::InvalidPiece),
         }
     }
 }
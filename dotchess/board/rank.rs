@@ -1,5 +1,4 @@
 use crate::common::{Error, Result};
-use alloc::format;
 use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
 use num_derive::{FromPrimitive, ToPrimitive};
 use scale::{Decode, Encode};
@@ -42,7 +41,7 @@ impl core::convert::TryFrom<u8> for Rank {
 
     fn try_from(value: u8) -> Result<Self> {
         num::FromPrimitive::from_u8(value)
-            .ok_or_else(|| Error::InvalidArgument(format!("Invalid Rank index: {}", value)))
+            .ok_or(Error::InvalidRank)
     }
 }
 
@@ -78,7 +77,7 @@ impl core::convert::TryFrom<char> for Rank {
             '6' => Ok(_6),
             '7' => Ok(_7),
             '8' => Ok(_8),
-            c => Err(Error::InvalidArgument(format!("Invalid Rank char: {}", c))),
+            _ => Err(Error::InvalidRank),
         }
     }
 }
@@ -1,5 +1,4 @@
 use crate::common::{Error, Result};
-use alloc::format;
 use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
 use num_derive::{FromPrimitive, ToPrimitive};
 use scale::{Decode, Encode};
@@ -35,8 +34,7 @@ impl core::convert::TryFrom<u8> for Side {
     type Error = Error;
 
     fn try_from(value: u8) -> Result<Self> {
-        num::FromPrimitive::from_u8(value)
-            .ok_or_else(|| Error::InvalidArgument(format!("Invalid Side index: {}", value)))
+        num::FromPrimitive::from_u8(value).ok_or(Error::InvalidSide)
     }
 }
 
@@ -60,10 +58,7 @@ impl core::convert::TryFrom<char> for Side {
         match value {
             'w' => Ok(White),
             'b' => Ok(Black),
-            n => Err(Error::InvalidArgument(format!(
-                "Invalid Side string: {}",
-                n
-            ))),
+            _ => Err(Error::InvalidSide),
         }
     }
 }
@@ -89,7 +84,7 @@ impl Side {
         match string {
             Self::WHITE_STRING => Ok(White),
             Self::BLACK_STRING => Ok(Black),
-            x => Err(Error::InvalidArgument(format!("Invalid Side: {}", x))),
+            _ => Err(Error::InvalidSide),
         }
     }
 
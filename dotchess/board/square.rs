@@ -1,6 +1,8 @@
 use super::file::File;
 use super::rank::Rank;
+use crate::common::{Error, Result};
 use core::convert::TryFrom;
+use core::convert::TryInto;
 use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
 use scale::{Decode, Encode};
 
@@ -38,6 +40,23 @@ impl core::convert::Into<Rank> for Square {
     }
 }
 
+impl core::convert::TryFrom<&str> for Square {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let mut chars = value.chars();
+
+        let file: File = chars.next().ok_or(Error::InvalidSquare)?.try_into()?;
+        let rank: Rank = chars.next().ok_or(Error::InvalidSquare)?.try_into()?;
+
+        if chars.next().is_some() {
+            return Err(Error::InvalidSquare);
+        }
+
+        Ok(Self::new(file, rank))
+    }
+}
+
 impl Square {
     pub const A1: Self = Self(0);
     pub const B1: Self = Self(1);
@@ -85,6 +104,15 @@ mod tests {
         assert_eq!(rank, Rank::_2);
     }
 
+    #[test]
+    fn square_from_str() {
+        let square: Square = "e4".try_into().unwrap();
+
+        assert_eq!(square, Square::new(File::E, Rank::_4));
+        assert!(Square::try_from("e9").is_err());
+        assert!(Square::try_from("e44").is_err());
+    }
+
     #[test]
     fn square_h8_index() {
         let index: u8 = Square::new(File::H, Rank::_8).into();
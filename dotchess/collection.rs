@@ -0,0 +1,209 @@
+//! Calls to PSP34 collection contracts finished games are minted to
+
+use crate::board::Side;
+use crate::common::Result;
+use crate::gameover::GameOverReason;
+use alloc::string::String;
+use alloc::vec::Vec;
+use ink_env::AccountId;
+use scale::{Decode, Encode};
+
+/// Metadata attribute of the PSP34 token the SCALE encoded record is stored in
+pub const RECORD_ATTRIBUTE: &[u8] = b"record";
+
+/// Record of a finished game minted as the PSP34 token `Collectible::id`
+///
+/// `side` is side of the token owner, in a draw both players get a token
+#[derive(Clone, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq, scale_info::TypeInfo))]
+pub struct Collectible {
+    pub game: AccountId,
+    pub side: Side,
+    /// Moves encoded by `Mov::encode`
+    pub moves: Vec<u16>,
+    pub start_fen: String,
+    pub winner: Option<Side>,
+    pub reason: GameOverReason,
+}
+
+impl Collectible {
+    /// Returns PSP34 token id, which is SCALE encoding of the game account and the side
+    pub fn id(&self) -> Id {
+        Id::Bytes((self.game, self.side).encode())
+    }
+}
+
+/// PSP34 token id
+#[derive(Clone, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq, scale_info::TypeInfo))]
+pub enum Id {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Bytes(Vec<u8>),
+}
+
+/// Error returned by PSP34 collection contracts
+#[derive(Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, scale_info::TypeInfo))]
+pub enum Psp34Error {
+    Custom(String),
+    SelfApprove,
+    NotApproved,
+    TokenExists,
+    TokenNotExists,
+    SafeTransferCheckFailed(String),
+}
+
+#[cfg(not(test))]
+mod psp34 {
+    use super::{Id, Psp34Error};
+    use crate::common::{Error, Result};
+    use alloc::vec::Vec;
+    use ink_env::call::{build_call, utils::ReturnType, ExecutionInput, Selector};
+    use ink_env::{AccountId, DefaultEnvironment};
+
+    /// Selector of `PSP34Mintable::mint`
+    const MINT_SELECTOR: [u8; 4] = [0x6c, 0x41, 0xf2, 0xec];
+    /// Selector of `PSP34Metadata::set_attribute`
+    const SET_ATTRIBUTE_SELECTOR: [u8; 4] = [0xb9, 0xdc, 0x9e, 0x4f];
+
+    pub fn mint(collection: AccountId, to: AccountId, id: Id) -> Result<()> {
+        build_call::<DefaultEnvironment>()
+            .callee(collection)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(MINT_SELECTOR))
+                    .push_arg(to)
+                    .push_arg(id),
+            )
+            .returns::<ReturnType<core::result::Result<(), Psp34Error>>>()
+            .fire()
+            .map_err(|_| Error::MintFailed)?
+            .map_err(|_| Error::MintFailed)
+    }
+
+    pub fn set_attribute(
+        collection: AccountId,
+        id: Id,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<()> {
+        build_call::<DefaultEnvironment>()
+            .callee(collection)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(SET_ATTRIBUTE_SELECTOR))
+                    .push_arg(id)
+                    .push_arg(key)
+                    .push_arg(value),
+            )
+            .returns::<ReturnType<core::result::Result<(), Psp34Error>>>()
+            .fire()
+            .map_err(|_| Error::MintFailed)?
+            .map_err(|_| Error::MintFailed)
+    }
+}
+
+/// Mock PSP34 collections, the off-chain environment cannot dispatch cross-contract calls
+#[cfg(test)]
+pub mod mock {
+    use super::{Id, Psp34Error};
+    use ink_env::AccountId;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::vec::Vec;
+
+    thread_local! {
+        static TOKENS: RefCell<HashMap<AccountId, Vec<(Id, AccountId)>>> =
+            RefCell::new(HashMap::new());
+        static ATTRIBUTES: RefCell<HashMap<AccountId, Vec<(Id, Vec<u8>, Vec<u8>)>>> =
+            RefCell::new(HashMap::new());
+    }
+
+    pub fn mint(collection: AccountId, to: AccountId, id: Id) -> Result<(), Psp34Error> {
+        TOKENS.with(|tokens| {
+            let mut tokens = tokens.borrow_mut();
+            let minted = tokens.entry(collection).or_default();
+
+            if minted.iter().any(|(minted_id, _)| *minted_id == id) {
+                return Err(Psp34Error::TokenExists);
+            }
+
+            minted.push((id, to));
+
+            Ok(())
+        })
+    }
+
+    pub fn set_attribute(
+        collection: AccountId,
+        id: Id,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), Psp34Error> {
+        ATTRIBUTES.with(|attributes| {
+            let mut attributes = attributes.borrow_mut();
+            let set = attributes.entry(collection).or_default();
+
+            set.retain(|(set_id, set_key, _)| *set_id != id || *set_key != key);
+            set.push((id, key, value));
+
+            Ok(())
+        })
+    }
+
+    /// Returns value of the `key` attribute of token `id` in `collection`
+    pub fn attribute(collection: AccountId, id: &Id, key: &[u8]) -> Option<Vec<u8>> {
+        ATTRIBUTES.with(|attributes| {
+            attributes.borrow().get(&collection).and_then(|set| {
+                set.iter()
+                    .find(|(set_id, set_key, _)| set_id == id && set_key.as_slice() == key)
+                    .map(|(_, _, value)| value.clone())
+            })
+        })
+    }
+
+    /// Removes all tokens minted in `collection`, returning their ids and owners
+    pub fn take_tokens(collection: AccountId) -> Vec<(Id, AccountId)> {
+        TOKENS.with(|tokens| tokens.borrow_mut().remove(&collection).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod psp34 {
+    use super::{mock, Id};
+    use crate::common::{Error, Result};
+    use alloc::vec::Vec;
+    use ink_env::AccountId;
+
+    pub fn mint(collection: AccountId, to: AccountId, id: Id) -> Result<()> {
+        mock::mint(collection, to, id).map_err(|_| Error::MintFailed)
+    }
+
+    pub fn set_attribute(
+        collection: AccountId,
+        id: Id,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<()> {
+        mock::set_attribute(collection, id, key, value).map_err(|_| Error::MintFailed)
+    }
+}
+
+/// Mints `collectible` to `to` in PSP34 `collection` and stores the record in its
+/// `RECORD_ATTRIBUTE` attribute, the calling contract must be allowed to mint
+/// and to set attributes
+pub fn mint(collection: AccountId, to: AccountId, collectible: &Collectible) -> Result<()> {
+    psp34::mint(collection, to, collectible.id())?;
+    psp34::set_attribute(
+        collection,
+        collectible.id(),
+        RECORD_ATTRIBUTE.into(),
+        collectible.encode(),
+    )
+}
@@ -1,25 +1,128 @@
-use alloc::string::String;
 use scale::{Decode, Encode};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
-#[derive(Encode, Decode, Debug, Clone)]
+#[derive(Encode, Decode, Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum Error {
-    InvalidArgument(String),
-    IllegalMove(String),
+    /// Caller is not allowed to perform the action
     InvalidCaller,
+    /// Piece on the origin square belongs to the side not in turn
+    NotYourTurn,
+    /// There is no piece on the origin square
+    EmptyOriginSquare,
+    /// Pawn move to the last rank is missing promotion piece
+    MissingPromotion,
+    /// Piece on the origin square cannot move to the target square
+    IllegalMove,
+    /// Move does not resolve check
+    KingInCheck,
+    /// Move exposes own king to check
+    MoveLeavesKingInCheck,
+    /// Game has already ended
+    GameAlreadyOver,
+    /// Game is waiting for players to deposit their stakes
+    GameNotFunded,
+    /// Game is no longer waiting for deposits
+    GameAlreadyFunded,
+    /// Game has not ended yet
+    GameNotOver,
+    /// Fee cannot be collected until the grace period after the end of the game passes
+    GracePeriodNotOver,
+    /// Side in turn still has time left
+    NotOutOfTime,
+    /// Transferred balance does not match the stake
+    InvalidDeposit,
+    /// Transfer of funds failed
+    TransferFailed,
+    /// Draw cannot be claimed in the current position
+    DrawNotClaimable,
+    /// There is no pending takeback request
+    NoTakebackRequest,
+    /// Delegate expiry block is not in the future
+    InvalidExpiry,
+    /// Game is not being played off-chain
+    ChannelNotOpen,
+    /// Game is being played off-chain, moves are submitted by `checkpoint`
+    ChannelOpen,
+    /// There is no pending checkpoint
+    NoCheckpoint,
+    /// Checkpoint does not extend the pending one
+    CheckpointNotLonger,
+    /// Dispute period of the pending checkpoint is over
+    DisputePeriodOver,
+    /// Dispute period of the pending checkpoint is not over yet
+    DisputePeriodNotOver,
+    /// Signature is malformed or was not made by the expected player
+    InvalidSignature,
+    /// Signed position does not match the position after the signed move
+    PositionMismatch,
+    /// Game clocks were paused by the arbiter
+    GamePaused,
+    /// Added time does not fit the clock
+    TimeOverflow,
+    /// Minting of a collectible failed
+    MintFailed,
+    /// There is no pending commitment for the current ply
+    NoCommitment,
+    /// Commitment can be revealed only in a block after it was made
+    RevealTooEarly,
+    /// Reveal window of the commitment has passed
+    RevealWindowClosed,
+    /// Revealed move or draw offer does not match the commitment
+    CommitmentMismatch,
+    /// There is no draw offer of the opponent to decline
+    NoDrawOffer,
+    /// Side has offered a draw too recently to offer another one
+    DrawOfferTooSoon,
+    /// Ply number out of range of the move history
+    InvalidPly,
+    /// Move string is malformed
+    InvalidMove,
+    /// Square string is malformed
+    InvalidSquare,
+    /// File index or char is invalid
+    InvalidFile,
+    /// Rank index or char is invalid
+    InvalidRank,
+    /// Piece index or char is invalid
+    InvalidPiece,
+    /// Side index, char or string is invalid
+    InvalidSide,
+    /// Game over reason index is invalid
+    InvalidGameOverReason,
+    /// FEN string is malformed in `field`
+    InvalidFen { field: FenField },
+    /// String formatting failed
+    FormatFailed,
     Other,
 }
 
+/// Field of a FEN string
+#[derive(Encode, Decode, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum FenField {
+    PiecePlacement,
+    ActiveColor,
+    CastlingRights,
+    EnPassant,
+    HalfmoveClock,
+    FullmoveNumber,
+}
+
 impl core::convert::From<ink_env::Error> for Error {
-    fn from(_: ink_env::Error) -> Self {
-        Self::Other
+    fn from(error: ink_env::Error) -> Self {
+        match error {
+            ink_env::Error::TransferFailed | ink_env::Error::BelowSubsistenceThreshold => {
+                Self::TransferFailed
+            }
+            _ => Self::Other,
+        }
     }
 }
 
 impl core::convert::From<core::fmt::Error> for Error {
     fn from(_: core::fmt::Error) -> Self {
-        Self::Other
+        Self::FormatFailed
     }
 }
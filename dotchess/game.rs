@@ -1,7 +1,7 @@
 use crate::board::{BitBoard, Board, File, Mov, Piece, Rank, Side, Square};
-use crate::common::{Error, Result};
+use crate::common::{Error, FenField, Result};
+use crate::gameover::GameOverReason;
 use crate::zobrist::ZobristHash;
-use alloc::format;
 use alloc::string::String;
 use bitintr::Tzcnt;
 use core::convert::TryFrom;
@@ -384,6 +384,41 @@ impl Game {
         false
     }
 
+    /// Returns winner and reason if the game ends in this position, which is on
+    /// checkmate, stalemate or insufficient mating material
+    pub fn position_result(&self) -> Option<(Option<Side>, GameOverReason)> {
+        if !self.has_legal_moves() {
+            if self.is_check() {
+                let winner = self.side_next_in_turn().flip();
+
+                return Some((Some(winner), GameOverReason::Checkmate));
+            }
+
+            return Some((None, GameOverReason::Stalemate));
+        }
+
+        if self.no_side_have_sufficient_mating_material() {
+            return Some((None, GameOverReason::InsufficientMatingMaterial));
+        }
+
+        None
+    }
+
+    /// Returns reason if the game is drawn without a claim, which is on fivefold
+    /// repetition or by the seventy-five move rule. The position occurred `repetitions`
+    /// times, counting this one.
+    pub fn forced_draw(&self, repetitions: u32) -> Option<GameOverReason> {
+        if repetitions >= 5 {
+            return Some(GameOverReason::FivefoldRepetition);
+        }
+
+        if self.halfmove_clock() >= 150 {
+            return Some(GameOverReason::SeventyFiveMoveRule);
+        }
+
+        None
+    }
+
     pub fn legal_moves_from(&self, from: Square) -> Vec<Mov> {
         let mut legal_moves = Vec::new();
         let is_pawn = self.board.is_pawn(from);
@@ -439,12 +474,143 @@ impl Game {
         legal_moves
     }
 
+    /// Returns side and piece standing on `square`
+    pub fn piece_at(&self, square: Square) -> Option<(Side, Piece)> {
+        self.board.piece_at(square)
+    }
+
+    /// Returns piece captured by move `mov`, including pawns captured en passant
+    pub fn captured_piece(&self, mov: &Mov) -> Option<Piece> {
+        let (side, piece) = self.board.piece_at(mov.from())?;
+
+        match self.board.piece_at(mov.to()) {
+            Some((target_side, target_piece)) if target_side != side => Some(target_piece),
+            Some(_) => None,
+            None => {
+                let from_file: File = mov.from().into();
+                let to_file: File = mov.to().into();
+
+                if piece == Piece::Pawn && from_file != to_file {
+                    Some(Piece::Pawn)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Returns move `mov` in standard algebraic notation
+    pub fn san(&self, mov: &Mov) -> Result<String> {
+        let (_, piece) = self
+            .board
+            .piece_at(mov.from())
+            .ok_or(Error::EmptyOriginSquare)?;
+
+        let from_file: File = mov.from().into();
+        let from_rank: Rank = mov.from().into();
+        let to_file: File = mov.to().into();
+        let to_rank: Rank = mov.to().into();
+
+        let is_capture = self.captured_piece(mov).is_some();
+
+        let mut san = String::new();
+
+        match piece {
+            Piece::King if (from_file as i8 - to_file as i8).abs() == 2 => {
+                match to_file {
+                    File::G => write!(&mut san, "O-O")?,
+                    _ => write!(&mut san, "O-O-O")?,
+                };
+            }
+            Piece::Pawn => {
+                if is_capture {
+                    write!(&mut san, "{}x", <File as Into<char>>::into(from_file))?;
+                }
+
+                write!(
+                    &mut san,
+                    "{}{}",
+                    <File as Into<char>>::into(to_file),
+                    <Rank as Into<char>>::into(to_rank)
+                )?;
+
+                if let Some(promotion) = mov.promotion() {
+                    let promotion: char = promotion.into();
+                    write!(&mut san, "={}", promotion.to_ascii_uppercase())?;
+                }
+            }
+            _ => {
+                let piece_char: char = piece.into();
+                write!(&mut san, "{}", piece_char.to_ascii_uppercase())?;
+
+                // Disambiguate between pieces of the same kind moving to the same square
+                let mut ambiguous = false;
+                let mut same_file = false;
+                let mut same_rank = false;
+
+                for other in self.legal_moves().iter() {
+                    if other.to() != mov.to() || other.from() == mov.from() {
+                        continue;
+                    }
+
+                    if let Some((_, other_piece)) = self.board.piece_at(other.from()) {
+                        if other_piece != piece {
+                            continue;
+                        }
+                    }
+
+                    ambiguous = true;
+                    same_file |= <Square as Into<File>>::into(other.from()) == from_file;
+                    same_rank |= <Square as Into<Rank>>::into(other.from()) == from_rank;
+                }
+
+                if ambiguous && (!same_file || same_rank) {
+                    write!(&mut san, "{}", <File as Into<char>>::into(from_file))?;
+                }
+
+                if ambiguous && same_file {
+                    write!(&mut san, "{}", <Rank as Into<char>>::into(from_rank))?;
+                }
+
+                if is_capture {
+                    write!(&mut san, "x")?;
+                }
+
+                write!(
+                    &mut san,
+                    "{}{}",
+                    <File as Into<char>>::into(to_file),
+                    <Rank as Into<char>>::into(to_rank)
+                )?;
+            }
+        }
+
+        let game_new = self.make_move(mov)?;
+
+        if game_new.is_check() {
+            if game_new.has_legal_moves() {
+                write!(&mut san, "+")?;
+            } else {
+                write!(&mut san, "#")?;
+            }
+        }
+
+        Ok(san)
+    }
+
     pub fn make_move(&self, mov: &Mov) -> Result<Self> {
+        // Assert sides turn
+        match self.board.piece_at(mov.from()) {
+            None => return Err(Error::EmptyOriginSquare),
+            Some((side, _)) if side != self.side_next_in_turn() => {
+                return Err(Error::NotYourTurn)
+            }
+            Some(_) => {}
+        }
+
         // Assert move is pseudo legal
         if (self.pseudo_legal_moves_from(mov.from()) & BitBoard::square(mov.to())).is_empty() {
-            let error_msg = format!("Invalid move {}", <Mov as Into<String>>::into(*mov));
-
-            return Err(Error::IllegalMove(error_msg));
+            return Err(Error::IllegalMove);
         }
 
         let (board, state, zhash, halfmove_clock, fullmove_number) =
@@ -452,7 +618,11 @@ impl Game {
 
         // Assert king not attacked
         if board.is_king_attacked(self.side_next_in_turn()) {
-            return Err(Error::IllegalMove(String::from("King in check")));
+            if self.is_check() {
+                return Err(Error::KingInCheck);
+            }
+
+            return Err(Error::MoveLeavesKingInCheck);
         }
 
         Ok(Self {
@@ -473,9 +643,7 @@ impl Game {
         halfmove_clock: &mut HalfmoveClock,
         fullmove_number: &mut FullmoveNumber,
     ) -> Result<()> {
-        let new_error = |desc: &str| {
-            Error::InvalidArgument(format!("Unable to parse FEN string: {} ({})", fen, desc))
-        };
+        let new_error = |field: FenField| Error::InvalidFen { field };
 
         let mut fen_chars = fen.chars();
 
@@ -485,7 +653,7 @@ impl Game {
         loop {
             let char = fen_chars
                 .nth(0)
-                .ok_or_else(|| new_error("unexpected end of string"))?;
+                .ok_or_else(|| new_error(FenField::PiecePlacement))?;
 
             if char.is_whitespace() {
                 break;
@@ -518,18 +686,18 @@ impl Game {
             }
 
             // Unexpected character
-            return Err(new_error(&format!("unexpected char: {}", char)));
+            return Err(new_error(FenField::PiecePlacement));
         }
 
         // Parse turn
         let char = fen_chars
             .nth(0)
-            .ok_or_else(|| new_error("unexpected end of string"))?;
+            .ok_or_else(|| new_error(FenField::ActiveColor))?;
 
         let side = match char {
             'w' => Side::White,
             'b' => Side::Black,
-            x => return Err(new_error(&format!("unexpected side char: {}", x))),
+            _ => return Err(new_error(FenField::ActiveColor)),
         };
 
         state.set_side_next_in_turn(side);
@@ -537,12 +705,12 @@ impl Game {
         // Parse castling rights
         fen_chars
             .advance_by(1)
-            .or_else(|_| Err(new_error("unexpected end of string")))?;
+            .or_else(|_| Err(new_error(FenField::CastlingRights)))?;
 
         loop {
             let char = fen_chars
                 .nth(0)
-                .ok_or_else(|| new_error("unexpected end of string"))?;
+                .ok_or_else(|| new_error(FenField::CastlingRights))?;
 
             if char.is_whitespace() {
                 break;
@@ -562,21 +730,21 @@ impl Game {
                 match char.to_ascii_lowercase() {
                     'q' => state.set_queen_side_castling_right(side, true),
                     'k' => state.set_king_side_castling_right(side, true),
-                    x => return Err(new_error(&format!("unexpected castling char: {}", x))),
+                    _ => return Err(new_error(FenField::CastlingRights)),
                 }
 
                 continue;
             }
 
             // Unexpected character
-            return Err(new_error(&format!("unexpected char: {}", char)));
+            return Err(new_error(FenField::CastlingRights));
         }
 
         // Parse en passants
         loop {
             let char = fen_chars
                 .nth(0)
-                .ok_or_else(|| new_error("unexpected end of string"))?;
+                .ok_or_else(|| new_error(FenField::EnPassant))?;
 
             if char.is_whitespace() {
                 break;
@@ -593,28 +761,22 @@ impl Game {
             }
 
             // Unexpected character
-            return Err(new_error(&format!("unexpected char: {}", char)));
+            return Err(new_error(FenField::EnPassant));
         }
 
         // Parse halfmove clock
         let halfmove_chars: String = fen_chars.by_ref().take_while(|c| c.is_numeric()).collect();
 
-        *halfmove_clock = halfmove_chars.parse::<u32>().map_err(|_| {
-            new_error(&format!(
-                "expected number for halfmove clock, got: {}",
-                halfmove_chars
-            ))
-        })?;
+        *halfmove_clock = halfmove_chars
+            .parse::<u32>()
+            .map_err(|_| new_error(FenField::HalfmoveClock))?;
 
         // Parse Fullmove number
         let fullmove_chars: String = fen_chars.by_ref().take_while(|c| c.is_numeric()).collect();
 
-        *fullmove_number = fullmove_chars.parse::<u32>().map_err(|_| {
-            new_error(&format!(
-                "expected number for fullmove number, got: {}",
-                fullmove_chars
-            ))
-        })?;
+        *fullmove_number = fullmove_chars
+            .parse::<u32>()
+            .map_err(|_| new_error(FenField::FullmoveNumber))?;
 
         Ok(())
     }
@@ -638,10 +800,10 @@ impl Game {
         let (side, piece) = self
             .board
             .piece_at(mov.from())
-            .ok_or(Error::InvalidArgument(format!("Origin square is empty")))?;
+            .ok_or(Error::EmptyOriginSquare)?;
 
         if side as u8 != self.side_next_in_turn() as u8 {
-            return Err(Error::InvalidArgument(format!("Not its turn")));
+            return Err(Error::NotYourTurn);
         }
 
         let from = mov.from();
@@ -722,8 +884,7 @@ impl Game {
 
                     // Is promotion?
                     let new_piece = if let Rank::_8 | Rank::_1 = rank_to {
-                        mov.promotion()
-                            .ok_or(Error::InvalidArgument(format!("Missing promotion choice")))?
+                        mov.promotion().ok_or(Error::MissingPromotion)?
                     } else {
                         piece
                     };
@@ -970,6 +1131,131 @@ mod tests {
         assert_eq!(game.fen().unwrap(), Game::FEN_NEW_GAME);
     }
 
+    #[ink::test]
+    fn san_knight_move() {
+        let game = Game::new(Game::FEN_NEW_GAME).unwrap();
+        let mov: Mov = "g1f3".try_into().unwrap();
+
+        assert_eq!(game.san(&mov).unwrap(), "Nf3");
+    }
+
+    #[ink::test]
+    fn san_pawn_capture() {
+        let game = Game::new("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2")
+            .unwrap();
+        let mov: Mov = "e4d5".try_into().unwrap();
+
+        assert_eq!(game.san(&mov).unwrap(), "exd5");
+    }
+
+    #[ink::test]
+    fn san_castling() {
+        let game = Game::new("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let king_side: Mov = "e1g1".try_into().unwrap();
+        let queen_side: Mov = "e1c1".try_into().unwrap();
+
+        assert_eq!(game.san(&king_side).unwrap(), "O-O");
+        assert_eq!(game.san(&queen_side).unwrap(), "O-O-O");
+    }
+
+    #[ink::test]
+    fn san_disambiguation_and_mate() {
+        let game = Game::new("6k1/5ppp/8/8/8/8/8/R3R1K1 w - - 0 1").unwrap();
+        let mov: Mov = "e1e8".try_into().unwrap();
+
+        assert_eq!(game.san(&mov).unwrap(), "Re8#");
+
+        let mov: Mov = "a1d1".try_into().unwrap();
+
+        assert_eq!(game.san(&mov).unwrap(), "Rad1");
+    }
+
+    #[ink::test]
+    fn captured_piece() {
+        let game = Game::new("4k3/8/8/3pP3/8/8/8/R3K2r w - d6 0 1").unwrap();
+
+        let en_passant: Mov = "e5d6".try_into().unwrap();
+        let capture: Mov = "e1f1".try_into().unwrap();
+        let rook_capture: Mov = "a1a8".try_into().unwrap();
+
+        assert_eq!(game.captured_piece(&en_passant), Some(Piece::Pawn));
+        assert_eq!(game.captured_piece(&capture), None);
+        assert_eq!(game.captured_piece(&rook_capture), None);
+        assert_eq!(game.piece_at(Square::H1), Some((Side::Black, Piece::Rook)));
+    }
+
+    #[test]
+    fn make_move_errors() {
+        let game = Game::new("4k3/P7/8/8/8/8/7r/4K3 w - - 0 1").unwrap();
+        let make_move = |mov: &str| game.make_move(&mov.try_into().unwrap()).err();
+
+        assert_eq!(make_move("d4d5"), Some(Error::EmptyOriginSquare));
+        assert_eq!(make_move("e8d8"), Some(Error::NotYourTurn));
+        assert_eq!(make_move("e1e3"), Some(Error::IllegalMove));
+        assert_eq!(make_move("a7a8"), Some(Error::MissingPromotion));
+        assert_eq!(make_move("a7a8Q"), None);
+    }
+
+    #[test]
+    fn make_move_king_in_check() {
+        let game = Game::new("4k3/8/8/8/8/8/4r3/R3K3 w - - 0 1").unwrap();
+        let make_move = |mov: &str| game.make_move(&mov.try_into().unwrap()).err();
+
+        assert_eq!(make_move("a1a2"), Some(Error::KingInCheck));
+    }
+
+    #[test]
+    fn make_move_leaves_king_in_check() {
+        let game = Game::new("4k3/8/8/8/8/4r3/4B3/4K3 w - - 0 1").unwrap();
+        let make_move = |mov: &str| game.make_move(&mov.try_into().unwrap()).err();
+
+        assert_eq!(make_move("e2d3"), Some(Error::MoveLeavesKingInCheck));
+    }
+
+    #[test]
+    fn position_result_and_forced_draw() {
+        let mate = Game::new("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+        let stalemate = Game::new("7k/8/6QK/8/8/8/8/8 b - - 0 1").unwrap();
+        let bare_kings = Game::new("7k/8/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+        let seventy_five = Game::new("7k/8/6K1/8/8/8/8/6R1 b - - 150 100").unwrap();
+
+        assert_eq!(
+            mate.position_result(),
+            Some((Some(Side::White), GameOverReason::Checkmate))
+        );
+        assert_eq!(
+            stalemate.position_result(),
+            Some((None, GameOverReason::Stalemate))
+        );
+        assert_eq!(
+            bare_kings.position_result(),
+            Some((None, GameOverReason::InsufficientMatingMaterial))
+        );
+        assert_eq!(seventy_five.position_result(), None);
+
+        assert_eq!(
+            seventy_five.forced_draw(1),
+            Some(GameOverReason::SeventyFiveMoveRule)
+        );
+        assert_eq!(
+            mate.forced_draw(5),
+            Some(GameOverReason::FivefoldRepetition)
+        );
+        assert_eq!(mate.forced_draw(4), None);
+    }
+
+    #[test]
+    fn invalid_fen_field() {
+        let error = Game::new("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1").err();
+
+        assert_eq!(
+            error,
+            Some(Error::InvalidFen {
+                field: FenField::ActiveColor
+            })
+        );
+    }
+
     #[ink::test]
     fn make_pseudo_legal_move_pawn_c2_to_d2() {
         let mov = Mov::new(10.into(), 18.into(), None);
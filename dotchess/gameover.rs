@@ -1,11 +1,22 @@
 use crate::common::Error;
-use alloc::format;
-use core::fmt::Write;
+use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
 use num_derive::{FromPrimitive, ToPrimitive};
 use scale::{Decode, Encode};
 
-#[derive(Encode, Decode, Debug, Copy, Clone, ToPrimitive, FromPrimitive)]
-#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+#[derive(
+    Encode,
+    Decode,
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    SpreadLayout,
+    PackedLayout,
+    ToPrimitive,
+    FromPrimitive,
+)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
 #[repr(u8)]
 pub enum GameOverReason {
     Checkmate = 0,
@@ -16,6 +27,13 @@ pub enum GameOverReason {
     FiftyMoveRule,
     Abandonment,
     DrawAgreement,
+    FivefoldRepetition,
+    SeventyFiveMoveRule,
+    Aborted,
+    Settlement,
+    Adjudication,
+    Equivocation,
+    Void,
 }
 
 impl core::convert::Into<u8> for GameOverReason {
@@ -28,9 +46,7 @@ impl core::convert::TryFrom<u8> for GameOverReason {
     type Error = Error;
 
     fn try_from(value: u8) -> core::result::Result<Self, Self::Error> {
-        num::FromPrimitive::from_u8(value).ok_or_else(|| {
-            Error::InvalidArgument(format!("Invalid GameOverReason index: {}", value))
-        })
+        num::FromPrimitive::from_u8(value).ok_or(Error::InvalidGameOverReason)
     }
 }
 
@@ -47,6 +63,13 @@ impl GameOverReason {
             FiftyMoveRule => "fifty move rule",
             Abandonment => "abandonment",
             DrawAgreement => "draw agreement",
+            FivefoldRepetition => "fivefold repetition",
+            SeventyFiveMoveRule => "seventy-five move rule",
+            Aborted => "aborted",
+            Settlement => "settlement",
+            Adjudication => "adjudication",
+            Equivocation => "equivocation",
+            Void => "void",
         }
     }
 }
@@ -0,0 +1,113 @@
+use crate::board::Side;
+use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
+use scale::{Decode, Encode};
+
+/// Odds the stronger player gives to the weaker one
+#[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+#[cfg_attr(
+    feature = "std",
+    derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+)]
+pub enum Handicap {
+    /// Black plays without the f7 pawn and white moves first
+    PawnAndMove,
+    /// `giver` plays without the queen's knight
+    KnightOdds { giver: Side },
+    /// `giver` plays without the queen
+    QueenOdds { giver: Side },
+    /// `receiver` starts with `amount` more blocks (or milliseconds) on the clock
+    ExtraTime { receiver: Side, amount: u32 },
+}
+
+impl Handicap {
+    /// FEN string of the starting position
+    pub fn fen(&self) -> &'static str {
+        use Handicap::*;
+
+        match *self {
+            PawnAndMove => "rnbqkbnr/ppppp1pp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            KnightOdds { giver: Side::White } => {
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R1BQKBNR w KQkq - 0 1"
+            }
+            KnightOdds { giver: Side::Black } => {
+                "r1bqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+            }
+            QueenOdds { giver: Side::White } => {
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNB1KBNR w KQkq - 0 1"
+            }
+            QueenOdds { giver: Side::Black } => {
+                "rnb1kbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+            }
+            ExtraTime { .. } => "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        }
+    }
+
+    /// Side giving the odds
+    pub fn giver(&self) -> Side {
+        use Handicap::*;
+
+        match *self {
+            PawnAndMove => Side::Black,
+            KnightOdds { giver } => giver,
+            QueenOdds { giver } => giver,
+            ExtraTime { receiver, .. } => receiver.flip(),
+        }
+    }
+
+    /// Blocks added to the time control of the `side` player,
+    /// see `TimeControl::with_extra_time`
+    pub fn extra_time(&self, side: Side) -> u32 {
+        match *self {
+            Handicap::ExtraTime { receiver, amount } if receiver == side => amount,
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{Piece, Square};
+    use crate::game::Game;
+    use core::convert::TryFrom;
+
+    fn piece_at(handicap: Handicap, square: &str) -> Option<(Side, Piece)> {
+        let game = Game::new(handicap.fen()).unwrap();
+
+        game.piece_at(Square::try_from(square).unwrap())
+    }
+
+    #[test]
+    fn odds_remove_pieces_of_giver() {
+        assert_eq!(piece_at(Handicap::PawnAndMove, "f7"), None);
+        assert_eq!(
+            piece_at(Handicap::KnightOdds { giver: Side::White }, "b1"),
+            None
+        );
+        assert_eq!(
+            piece_at(Handicap::KnightOdds { giver: Side::Black }, "b8"),
+            None
+        );
+        assert_eq!(
+            piece_at(Handicap::QueenOdds { giver: Side::White }, "d1"),
+            None
+        );
+        assert_eq!(
+            piece_at(Handicap::QueenOdds { giver: Side::Black }, "d8"),
+            None
+        );
+    }
+
+    #[test]
+    fn extra_time_goes_to_receiver() {
+        let handicap = Handicap::ExtraTime {
+            receiver: Side::Black,
+            amount: 30,
+        };
+
+        assert_eq!(handicap.giver(), Side::White);
+        assert_eq!(handicap.extra_time(Side::White), 0);
+        assert_eq!(handicap.extra_time(Side::Black), 30);
+        assert_eq!(handicap.fen(), Game::FEN_NEW_GAME);
+    }
+}
@@ -5,29 +5,53 @@ extern crate alloc;
 extern crate num;
 
 mod board;
+mod collection;
 mod common;
 mod game;
 mod gameover;
+mod handicap;
+mod signature;
+mod status;
+mod timecontrol;
+mod token;
 mod zobrist;
 
-pub use crate::board::Mov;
+pub use crate::board::{Mov, Piece, Side, Square};
+pub use crate::collection::Collectible;
 pub use crate::common::{Error, Result};
+pub use crate::dotchess::event_index;
+pub use crate::dotchess::{
+    Clocks, Commitment, Delegate, DotChess, DrawOfferChange, MoveFlags, Permissions, Settings,
+    SignedMove,
+};
 pub use crate::game::Game;
+pub use crate::gameover::GameOverReason;
+pub use crate::handicap::Handicap;
+pub use crate::signature::Signature;
+pub use crate::status::Status;
+pub use crate::timecontrol::{ClockUnit, TimeControl};
+pub use crate::zobrist::ZobristHash;
 
 use ink_lang as ink;
 
 #[ink::contract]
 mod dotchess {
 
-    use crate::board::{Mov, Side};
+    use crate::board::{Mov, Piece, Side, Square};
+    use crate::collection::{self, Collectible};
     use crate::common::{Error, Result};
     use crate::game::Game;
     use crate::gameover::GameOverReason;
+    use crate::handicap::Handicap;
+    use crate::signature::Signature;
+    use crate::status::Status;
+    use crate::timecontrol::{ClockUnit, TimeControl};
+    use crate::token;
     use crate::zobrist::ZobristHash;
-    use alloc::format;
     use alloc::string::String;
     use core::convert::TryInto;
-    use ink_storage::collections::HashMap;
+    use core::fmt::Write;
+    use ink_env::hash::Blake2x256;
     use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
     use ink_storage::{Box, Pack, Vec};
     use scale::{Decode, Encode};
@@ -37,52 +61,216 @@ mod dotchess {
         212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88,
         133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125,
     ];
+    /// Number of blocks after the end of the game before the fee can be collected, other
+    /// contracts have this long to read the result before the game contract is removed
+    const FEE_GRACE_PERIOD: BlockNumber = 14_400;
+    /// Number of blocks after the commitment block in which the commitment can be revealed
+    const REVEAL_WINDOW: BlockNumber = 10;
+    /// Number of plies that have to be made before a side can offer a draw again
+    const DRAW_OFFER_INTERVAL: u32 = 6;
+    /// Number of blocks after a checkpoint in which it can be replaced by a longer one
+    const DISPUTE_PERIOD: BlockNumber = 100;
+
+    /// SCALE indices of the events, emitted events are encoded as variants of an enum
+    /// in the order they are declared below
+    pub mod event_index {
+        pub const DRAW_OFFER_UPDATE: u8 = 0;
+        pub const BOARD_UPDATE: u8 = 1;
+        pub const TAKEBACK_REQUEST: u8 = 2;
+        pub const TAKEBACK_ACCEPT: u8 = 3;
+        pub const TAKEBACK_DECLINE: u8 = 4;
+        pub const DELEGATE_UPDATE: u8 = 5;
+        pub const CHANNEL_UPDATE: u8 = 6;
+        pub const CLOCK_PAUSE: u8 = 7;
+        pub const TIME_ADDED: u8 = 8;
+        pub const ADJUDICATED: u8 = 9;
+        pub const COLLECTIBLE_MINTED: u8 = 10;
+        pub const COMMITTED: u8 = 11;
+        pub const TIMEOUT_REPORTED: u8 = 12;
+        pub const CHECKPOINT_SUBMITTED: u8 = 13;
+        pub const GAME_OVER: u8 = 14;
+    }
 
     /// Event emitted when there is a draw offer proposed (or withdrawn)
-    /// by the `side` {"white","black"} player
+    /// by the `side` player
     ///
-    /// `offer` is true when the offer stands, false when it is withdrawn
+    /// `offer` is true when the offer stands, false when it no longer does,
+    /// `change` tells what happened to the offer
     #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
     pub struct DrawOfferUpdate {
         #[ink(topic)]
-        side: String,
+        side: Side,
         offer: bool,
+        change: DrawOfferChange,
     }
 
-    /// Event emitted when player makes a move `last_move`. It also contains blocks left
-    /// for that player
+    /// Event emitted when player makes a move `last_move`. It also contains time left
+    /// for that player in `clock_unit`
     ///
-    /// After this event it is `next_side` {"white","black"} players move which must take
-    /// place at latest in `next_move_block_deadline` block
+    /// After this event it is `next_side` players move which must take
+    /// place at latest in `next_move_deadline` block, or at `next_move_deadline` timestamp
+    /// if the game clock runs in milliseconds
     ///
     /// `fen` contains FEN string of board after the last move was made
+    ///
+    /// `ply` is number of plies made so far, `san` is the move in standard algebraic
+    /// notation, `piece` is the moving piece and `captured` the piece it captured.
+    /// `zobrist` is hash of the resulting position and `white_time_left`
+    /// with `black_time_left` are both clocks after the move
     #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
     pub struct BoardUpdate {
         #[ink(topic)]
-        next_side: String,
-        next_move_block_deadline: u32,
-        last_move: String,
-        last_side_blocks_left: u32,
+        next_side: Side,
+        clock_unit: ClockUnit,
+        next_move_deadline: u64,
+        last_move: Mov,
+        last_side_time_left: u32,
+        fen: String,
+        ply: u32,
+        san: String,
+        flags: MoveFlags,
+        piece: Piece,
+        captured: Option<Piece>,
+        zobrist: ZobristHash,
+        white_time_left: u32,
+        black_time_left: u32,
+    }
+
+    /// Event emitted when the `side` player requests a takeback of the last `n_plies` plies
+    #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct TakebackRequest {
+        #[ink(topic)]
+        side: Side,
+        n_plies: u32,
+    }
+
+    /// Event emitted when takeback requested by the `side` player
+    /// is accepted
+    ///
+    /// `fen` contains FEN string of board after the plies were taken back
+    #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct TakebackAccept {
+        #[ink(topic)]
+        side: Side,
+        n_plies: u32,
         fen: String,
     }
 
+    /// Event emitted when takeback requested by the `side` player
+    /// is declined
+    ///
+    /// `expired` is true when the request was not declined explicitly but expired
+    /// because the opponent has moved on
+    #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct TakebackDecline {
+        #[ink(topic)]
+        side: Side,
+        expired: bool,
+    }
+
+    /// Event emitted when the `side` player authorizes `delegate` to act on their behalf
+    /// until the `expires` block, or revokes the authorization if `delegate` is none
+    #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct DelegateUpdate {
+        #[ink(topic)]
+        side: Side,
+        delegate: Option<AccountId>,
+        expires: BlockNumber,
+    }
+
+    /// Event emitted when the game moves off-chain, `open` is true once both players
+    /// agree to play off-chain and false when moves are checkpointed back on-chain
+    #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct ChannelUpdate {
+        open: bool,
+    }
+
+    /// Event emitted when the arbiter pauses (`paused` is true) or resumes the clocks
+    #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct ClockPause {
+        paused: bool,
+    }
+
+    /// Event emitted when the arbiter adds `amount` of time to the `side` players clock
+    #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct TimeAdded {
+        #[ink(topic)]
+        side: Side,
+        amount: u32,
+    }
+
+    /// Event emitted when the arbiter adjudicates the game, `winner` is none for a draw
+    ///
+    /// `void` is true when the game was voided and stakes were refunded
+    #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct Adjudicated {
+        winner: Option<Side>,
+        void: bool,
+    }
+
+    /// Event emitted when a collectible of the finished game is minted
+    /// to the `owner` in PSP34 `collection`
+    #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct CollectibleMinted {
+        #[ink(topic)]
+        owner: AccountId,
+        collection: AccountId,
+    }
+
+    /// Event emitted when `side` commits to a move or draw offer for the `ply`th ply,
+    /// the commitment has to be revealed by the `reveal_deadline` block
+    #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct Committed {
+        #[ink(topic)]
+        side: Side,
+        ply: u32,
+        reveal_deadline: BlockNumber,
+    }
+
+    /// Event emitted when a third party `reporter` ends a timed out game and is paid `bounty`
+    #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct TimeoutReported {
+        #[ink(topic)]
+        reporter: AccountId,
+        bounty: Balance,
+    }
+
+    /// Event emitted when the `side` player checkpoints `plies` plies played off-chain,
+    /// a longer sequence can replace it until the `dispute_deadline` block
+    #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct CheckpointSubmitted {
+        #[ink(topic)]
+        side: Side,
+        plies: u32,
+        dispute_deadline: BlockNumber,
+    }
+
     /// Event emitted when game ended due to `reason`
     ///
-    /// Unless the game is drawn, there is also a `winner` {"white","black"}
+    /// Unless the game is drawn, there is also a `winner`, `handicap` is set for odds games
     ///
-    /// Possible values for `reason` are:
-    ///   - "checkmate"
-    ///   - "stalemate"
-    ///   - "insufficient mating material"
-    ///   - "resignation"
-    ///   - "threefold repetition"
-    ///   - "fifty move rule"
-    ///   - "abandonment"
-    ///   - "draw agreement"
+    /// `reason` is encoded as a `GameOverReason` variant index, `GameOverReason::as_str`
+    /// gives its human-readable form, e.g. "checkmate" or "threefold repetition"
     #[ink(event)]
+    #[cfg_attr(feature = "std", derive(Debug))]
     pub struct GameOver {
-        winner: Option<String>,
-        reason: String,
+        winner: Option<Side>,
+        reason: GameOverReason,
+        handicap: Option<Handicap>,
     }
 
     #[derive(Encode, Decode, SpreadLayout, PackedLayout)]
@@ -97,7 +285,178 @@ mod dotchess {
         black_blocks_left: u32,
         white_draw_offer: bool,
         black_draw_offer: bool,
+        clock_unit: ClockUnit,
         last_move_block: BlockNumber,
+        last_move_timestamp: Timestamp,
+        takeback_side: Option<Side>,
+        takeback_ply: u32,
+        stake: Balance,
+        white_deposited: bool,
+        black_deposited: bool,
+        status: Status,
+        finished_block: BlockNumber,
+        white_delegate: Option<Delegate>,
+        black_delegate: Option<Delegate>,
+        white_channel: bool,
+        black_channel: bool,
+        checkpoint_deadline: Option<BlockNumber>,
+        arbiter: Option<AccountId>,
+        paused: bool,
+        paused_block: BlockNumber,
+        paused_timestamp: Timestamp,
+        token: Option<AccountId>,
+        collection: Option<AccountId>,
+        white_commitment: Option<Commitment>,
+        black_commitment: Option<Commitment>,
+        reporter_bounty: Balance,
+        white_draw_offer_ply: Option<u32>,
+        black_draw_offer_ply: Option<u32>,
+        handicap: Option<Handicap>,
+    }
+
+    /// Actions a delegate may perform on behalf of a player, resigning
+    /// and handling funds is always reserved to the player
+    #[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    pub struct Permissions {
+        pub make_move: bool,
+        pub offer_draw: bool,
+        pub claim_draw: bool,
+        pub takeback: bool,
+    }
+
+    /// Session account allowed to act on behalf of a player before the `expires` block
+    #[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    pub struct Delegate {
+        pub account: AccountId,
+        pub expires: BlockNumber,
+        pub permissions: Permissions,
+    }
+
+    /// Move played off-chain
+    ///
+    /// `signature` is an ECDSA signature over `DotChess::signed_move_hash` made by
+    /// the player whose move it was, `zobrist` is hash of the position after the move
+    #[derive(Copy, Clone, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq, scale_info::TypeInfo))]
+    pub struct SignedMove {
+        pub ply: u32,
+        pub mov: Mov,
+        pub zobrist: ZobristHash,
+        pub signature: Signature,
+    }
+
+    /// Optional settings of a game, see `DotChess::with_settings`
+    #[derive(Copy, Clone, Default, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq, scale_info::TypeInfo))]
+    pub struct Settings {
+        /// Amount each player deposits before the game starts, zero starts it right away
+        pub stake: Balance,
+        /// Account who can adjudicate the result, pause the clocks or add time to a player
+        pub arbiter: Option<AccountId>,
+        /// PSP22 token the stake is paid in instead of native balance, players have to
+        /// approve the game contract to spend their stake before depositing it
+        pub token: Option<AccountId>,
+        /// PSP34 collection the finished game is minted to as a collectible
+        pub collection: Option<AccountId>,
+        /// Bounty paid out of the fee to a third party reporting a timeout,
+        /// see `DotChess::report_timeout`
+        pub reporter_bounty: Balance,
+        /// Odds given in the game, which has to start from the position of the handicap
+        pub handicap: Option<Handicap>,
+    }
+
+    /// Hash of a move or draw offer committed to by a side in block `block`,
+    /// see `DotChess::commit`
+    #[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    pub struct Commitment {
+        pub ply: u32,
+        pub hash: [u8; 32],
+        pub block: BlockNumber,
+    }
+
+    /// Action that can be delegated
+    #[derive(Copy, Clone)]
+    enum Action {
+        MakeMove,
+        OfferDraw,
+        ClaimDraw,
+        Takeback,
+    }
+
+    impl Permissions {
+        fn allows(&self, action: Action) -> bool {
+            match action {
+                Action::MakeMove => self.make_move,
+                Action::OfferDraw => self.offer_draw,
+                Action::ClaimDraw => self.claim_draw,
+                Action::Takeback => self.takeback,
+            }
+        }
+    }
+
+    /// What happened to a draw offer
+    #[derive(Copy, Clone, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq, scale_info::TypeInfo))]
+    pub enum DrawOfferChange {
+        /// Offer was made
+        Offered,
+        /// Offer was withdrawn by the offering side
+        Withdrawn,
+        /// Offer was declined by the opponent
+        Declined,
+        /// Opponent made a move instead of accepting the offer
+        Expired,
+    }
+
+    /// Properties of a move
+    #[derive(Copy, Clone, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq, scale_info::TypeInfo))]
+    pub struct MoveFlags {
+        pub capture: bool,
+        pub check: bool,
+        pub checkmate: bool,
+    }
+
+    /// Clocks of both players, time left is in `clock_unit`
+    ///
+    /// Side next in turn must move at latest in `next_move_deadline` block,
+    /// or at `next_move_deadline` timestamp if the game clock runs in milliseconds
+    #[derive(Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq, scale_info::TypeInfo))]
+    pub struct Clocks {
+        pub clock_unit: ClockUnit,
+        pub white_time_left: u32,
+        pub black_time_left: u32,
+        pub next_move_deadline: u64,
+    }
+
+    /// Move history entry
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    struct Ply {
+        /// Move encoded by `Mov::encode`
+        mov: u16,
+        /// Block number the move was made in
+        block: BlockNumber,
+        /// White blocks left before the move was made
+        white_blocks_left: u32,
+        /// Black blocks left before the move was made
+        black_blocks_left: u32,
     }
 
     #[ink(storage)]
@@ -108,8 +467,14 @@ mod dotchess {
         info: Pack<Info>,
         /// Zobrist hash history
         zobrist: Pack<Box<Vec<ZobristHash>>>,
-        /// Block increment per move
-        block_increment: u32,
+        /// Time control
+        time_control: Pack<TimeControl>,
+        /// FEN string of the starting position
+        start_fen: Pack<String>,
+        /// Move history
+        history: Pack<Box<Vec<Ply>>>,
+        /// Moves of the pending checkpoint encoded by `Mov::encode`
+        checkpoint: Pack<Box<Vec<u16>>>,
     }
 
     impl DotChess {
@@ -141,7 +506,75 @@ mod dotchess {
             block_increment: u32,
             fen: String,
         ) -> Self {
+            let time_control = TimeControl::Classic {
+                base: block_base,
+                increment: block_increment,
+            };
+
+            Self::with_time_control(white, black, time_control, ClockUnit::Blocks, fen)
+        }
+
+        /// Initiates new game from given `fen` string with given `time_control`,
+        /// the clock runs in `clock_unit`
+        #[ink(constructor)]
+        pub fn with_time_control(
+            white: AccountId,
+            black: AccountId,
+            time_control: TimeControl,
+            clock_unit: ClockUnit,
+            fen: String,
+        ) -> Self {
+            Self::with_stake(white, black, time_control, clock_unit, 0, fen)
+        }
+
+        /// Initiates new game like `with_time_control`, the game starts once both
+        /// players deposit `stake`. Zero `stake` starts the game right away.
+        #[ink(constructor)]
+        pub fn with_stake(
+            white: AccountId,
+            black: AccountId,
+            time_control: TimeControl,
+            clock_unit: ClockUnit,
+            stake: Balance,
+            fen: String,
+        ) -> Self {
+            let settings = Settings {
+                stake,
+                ..Settings::default()
+            };
+
+            Self::with_settings(white, black, time_control, clock_unit, fen, settings)
+        }
+
+        /// Initiates new game like `with_time_control` with given `settings`
+        #[ink(constructor)]
+        pub fn with_settings(
+            white: AccountId,
+            black: AccountId,
+            time_control: TimeControl,
+            clock_unit: ClockUnit,
+            fen: String,
+            settings: Settings,
+        ) -> Self {
+            let Settings {
+                stake,
+                arbiter,
+                token,
+                collection,
+                reporter_bounty,
+                handicap,
+            } = settings;
+
+            if let Some(handicap) = handicap {
+                assert!(
+                    fen == handicap.fen(),
+                    "odds game must start from the handicap position"
+                );
+            }
+
             let game = Game::new(fen.as_str()).unwrap();
+            let side_time_control =
+                |side| Self::handicap_time_control(time_control, handicap, side);
 
             let mut zobrist = Vec::new();
             zobrist.push(game.zobrist());
@@ -149,312 +582,3345 @@ mod dotchess {
             let info = Info {
                 white_account: white,
                 black_account: black,
-                white_blocks_left: block_base,
-                black_blocks_left: block_base,
+                white_blocks_left: side_time_control(Side::White).base(),
+                black_blocks_left: side_time_control(Side::Black).base(),
                 white_draw_offer: false,
                 black_draw_offer: false,
+                clock_unit,
                 last_move_block: Self::env().block_number(),
+                last_move_timestamp: Self::env().block_timestamp(),
+                takeback_side: None,
+                takeback_ply: 0,
+                stake,
+                white_deposited: stake == 0,
+                black_deposited: stake == 0,
+                status: if stake == 0 {
+                    Status::Active
+                } else {
+                    Status::Funding
+                },
+                finished_block: 0,
+                white_delegate: None,
+                black_delegate: None,
+                white_channel: false,
+                black_channel: false,
+                checkpoint_deadline: None,
+                arbiter,
+                paused: false,
+                paused_block: 0,
+                paused_timestamp: 0,
+                token,
+                collection,
+                white_commitment: None,
+                black_commitment: None,
+                reporter_bounty,
+                white_draw_offer_ply: None,
+                black_draw_offer_ply: None,
+                handicap,
             };
 
             Self {
                 game: Pack::new(game),
                 info: Pack::new(info),
                 zobrist: Pack::new(Box::new(zobrist)),
-                block_increment,
+                time_control: Pack::new(time_control),
+                start_fen: Pack::new(fen),
+                history: Pack::new(Box::new(Vec::new())),
+                checkpoint: Pack::new(Box::new(Vec::new())),
             }
         }
 
+        /// Initiates new odds game like `with_settings` where the `handicap` giver plays
+        /// from a weaker starting position or with less time than the opponent
+        #[ink(constructor)]
+        pub fn with_handicap(
+            white: AccountId,
+            black: AccountId,
+            time_control: TimeControl,
+            clock_unit: ClockUnit,
+            handicap: Handicap,
+            settings: Settings,
+        ) -> Self {
+            let settings = Settings {
+                handicap: Some(handicap),
+                ..settings
+            };
+
+            Self::with_settings(
+                white,
+                black,
+                time_control,
+                clock_unit,
+                handicap.fen().into(),
+                settings,
+            )
+        }
+
+        /// Returns odds given in this game, if any
+        #[ink(message)]
+        pub fn handicap(&self) -> Option<Handicap> {
+            self.info.handicap
+        }
+
         /// Returns FEN string representation of the board
         #[ink(message)]
         pub fn fen(&self) -> Result<String> {
             Ok(self.game.fen()?)
         }
 
-        /// Returns number of blocks given side has left
+        /// Returns number of blocks (or milliseconds, see `clock_unit`) given side has left
         #[ink(message)]
-        pub fn blocks_left(&self, side: String) -> Result<u32> {
-            let side = Side::from_str(side)?;
-
-            Ok(self.side_blocks_left(side))
+        pub fn blocks_left(&self, side: Side) -> u32 {
+            self.side_blocks_left(side)
         }
 
-        /// Makes a move `mov` formatted as [FROM FILE][FROM RANK][TO FILE][TO RANK][PROMO PIECE?]
-        ///
-        /// Codes for promotion pieces are:
-        ///   - N = knight
-        ///   - B = bishop
-        ///   - R = rook
-        ///   - Q = queen
-        ///
-        /// Example move for a pawn-to-queen promotion move from A7 to A8 is denoted as a7a8Q
+        /// Returns unit the game clock runs in
         #[ink(message)]
-        pub fn make_move(&mut self, mov: String) -> Result<()> {
-            let us_side = self.game.side_next_in_turn();
+        pub fn clock_unit(&self) -> ClockUnit {
+            self.info.clock_unit
+        }
 
-            if !self.side_belongs_to_caller(us_side) {
-                return Err(Error::InvalidCaller);
-            }
+        /// Returns legal moves of the side next in turn
+        #[ink(message)]
+        pub fn legal_moves(&self) -> alloc::vec::Vec<Mov> {
+            self.game.legal_moves().iter().copied().collect()
+        }
 
-            if self.side_blocks_left(us_side) == 0 {
-                return self.terminate_game_out_of_blocks(us_side);
+        /// Returns legal moves from `square`, which are none unless there is a piece
+        /// of the side next in turn
+        #[ink(message)]
+        pub fn legal_moves_from(&self, square: Square) -> alloc::vec::Vec<Mov> {
+            match self.game.piece_at(square) {
+                Some((side, _)) if side == self.game.side_next_in_turn() => {
+                    self.game.legal_moves_from(square).iter().copied().collect()
+                }
+                _ => alloc::vec::Vec::new(),
             }
+        }
 
-            let moov: Mov = mov.as_str().try_into()?;
-
-            // Make move
-            let game_new = self.game.make_move(&moov)?;
+        /// Returns true if the side next in turn is in check
+        #[ink(message)]
+        pub fn is_check(&self) -> bool {
+            self.game.is_check()
+        }
 
-            // Opponent out of moves?
-            if !game_new.has_legal_moves() {
-                if game_new.is_check() {
-                    return self.terminate_game(Some(us_side), GameOverReason::Checkmate);
-                }
+        /// Returns side next in turn
+        #[ink(message)]
+        pub fn side_to_move(&self) -> Side {
+            self.game.side_next_in_turn()
+        }
 
-                return self.terminate_game(None, GameOverReason::Stalemate);
-            }
+        /// Returns status of the game
+        #[ink(message)]
+        pub fn status(&self) -> Status {
+            self.info.status
+        }
 
-            // Is insufficient mating material?
-            if self.game.no_side_have_sufficient_mating_material() {
-                return self.terminate_game(None, GameOverReason::InsufficientMatingMaterial);
-            }
+        /// Returns clocks of both players
+        #[ink(message)]
+        pub fn clocks(&self) -> Clocks {
+            let next_side = self.game.side_next_in_turn();
 
-            // If halfmove clock resets, clear zobrist history
-            if game_new.halfmove_clock() == 0 {
-                self.zobrist.clear();
+            Clocks {
+                clock_unit: self.info.clock_unit,
+                white_time_left: self.side_blocks_left(Side::White),
+                black_time_left: self.side_blocks_left(Side::Black),
+                next_move_deadline: self.clock_now() + self.side_blocks_left(next_side) as u64,
             }
+        }
 
-            // Add zobrist to history
-            self.zobrist.push(game_new.zobrist());
-
-            // Update blocks left (must go before updating block number)
-            let block_diff = self.block_diff_since_last_move();
+        /// Returns accounts of white and black players
+        #[ink(message)]
+        pub fn players(&self) -> (AccountId, AccountId) {
+            (self.info.white_account, self.info.black_account)
+        }
 
-            let blocks_left_ref = match us_side {
-                Side::White => &mut self.info.white_blocks_left,
-                Side::Black => &mut self.info.black_blocks_left,
+        /// Deposits callers stake, the game starts once both players have deposited
+        ///
+        /// In games staked in a PSP22 token, the stake is transferred from the caller
+        /// and no native balance may be transferred along
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) -> Result<()> {
+            self.ensure_funding()?;
+
+            let stake = self.info.stake;
+            let native_stake = match self.info.token {
+                Some(_) => 0,
+                None => stake,
             };
 
-            if game_new.fullmove_number() > 40 {
-                *blocks_left_ref += self.block_increment;
+            if self.env().transferred_balance() != native_stake {
+                return Err(Error::InvalidDeposit);
             }
 
-            *blocks_left_ref -= block_diff;
-
-            let last_side_blocks_left = *blocks_left_ref;
+            let caller = self.env().caller();
 
-            // Update game and last move block number
-            self.game = ink_storage::Pack::new(game_new);
-            self.info.last_move_block = self.env().block_number();
+            let side = if caller == self.info.white_account && !self.info.white_deposited {
+                Side::White
+            } else if caller == self.info.black_account && !self.info.black_deposited {
+                Side::Black
+            } else {
+                return Err(Error::InvalidCaller);
+            };
 
-            // Check if player has no blocks left after this move
-            if self.side_blocks_left(us_side) == 0 {
-                return self.terminate_game_out_of_blocks(us_side);
+            if let Some(token) = self.info.token {
+                token::transfer_from(token, caller, self.env().account_id(), stake)?;
             }
 
-            // Check for threefold repetition
-            if self.max_repetition() >= 3 {
-                return self.terminate_game(None, GameOverReason::ThreefoldRepetition);
+            match side {
+                Side::White => self.info.white_deposited = true,
+                Side::Black => self.info.black_deposited = true,
             }
 
-            // Check fifty move rule
-            if self.game.halfmove_clock() >= 100 {
-                return self.terminate_game(None, GameOverReason::FiftyMoveRule);
+            if self.info.white_deposited && self.info.black_deposited {
+                self.info.status = Status::Active;
+                self.info.last_move_block = self.env().block_number();
+                self.info.last_move_timestamp = self.env().block_timestamp();
             }
 
-            let op_side = us_side.flip();
-
-            let next_move_block_deadline =
-                self.side_blocks_left(op_side) + self.env().block_number();
-
-            // Emit event
-            self.env().emit_event(BoardUpdate {
-                next_side: String::from(op_side.as_str()),
-                next_move_block_deadline,
-                last_move: mov,
-                last_side_blocks_left,
-                fen: self.game.fen()?,
-            });
-
             Ok(())
         }
 
-        /// Reports that next side has abandoned the match
+        /// Aborts game that is not funded yet, returning deposits
         #[ink(message)]
-        pub fn report_abandonment(&mut self) -> Result<()> {
-            let next_side = self.game.side_next_in_turn();
+        pub fn abort(&mut self) -> Result<()> {
+            self.ensure_funding()?;
 
-            if self.side_blocks_left(next_side) == 0 {
-                return self.terminate_game_out_of_blocks(next_side);
+            if self.caller_side().is_none() {
+                return Err(Error::InvalidCaller);
             }
 
-            let error_message = format!("{} not out of blocks", String::from(next_side.as_str()));
-
-            Err(Error::InvalidArgument(error_message))
+            self.refund_game(GameOverReason::Aborted)
         }
 
-        /// If `offer` is true, proposes draw to the opposing player,
-        /// otherwise withdraws any previous draw offers
+        /// Returns PSP22 token the stakes are paid in, none if they are paid in native balance
         #[ink(message)]
-        pub fn offer_draw(&mut self, offer: bool) -> Result<()> {
-            let next_side = self.game.side_next_in_turn();
+        pub fn token(&self) -> Option<AccountId> {
+            self.info.token
+        }
 
-            if !self.side_belongs_to_caller(next_side) {
-                return Err(Error::InvalidCaller);
-            }
+        /// Returns PSP34 collection finished game is minted to, if there is one
+        #[ink(message)]
+        pub fn collection(&self) -> Option<AccountId> {
+            self.info.collection
+        }
 
-            if self.side_blocks_left(next_side) == 0 {
-                return self.terminate_game_out_of_blocks(next_side);
-            }
+        /// Returns the arbiter of the game, if there is one
+        #[ink(message)]
+        pub fn arbiter(&self) -> Option<AccountId> {
+            self.info.arbiter
+        }
 
-            if offer && self.get_side_draw_offer(next_side.flip()) {
-                return self.terminate_game(None, GameOverReason::DrawAgreement);
-            }
+        /// Ends the game as adjudicated by the arbiter, `winner` is none for a draw
+        #[ink(message)]
+        pub fn adjudicate(&mut self, winner: Option<Side>) -> Result<()> {
+            self.ensure_arbiter()?;
 
-            self.set_side_draw_offer(next_side, offer);
+            self.ensure_in_progress()?;
 
-            self.env().emit_event(DrawOfferUpdate {
-                side: String::from(next_side.as_str()),
-                offer,
-            });
+            self.env().emit_event(Adjudicated { winner, void: false });
 
-            Ok(())
+            self.terminate_game(winner, GameOverReason::Adjudication)
         }
 
-        /// Returns true if `side` {"white","black"} proposes a draw, false otherwise
+        /// Voids the game by the arbiter, refunding deposited stakes
         #[ink(message)]
-        pub fn side_draw_offer(&self, side: String) -> Result<bool> {
-            let side = Side::from_str(side)?;
+        pub fn void(&mut self) -> Result<()> {
+            self.ensure_arbiter()?;
 
-            Ok(self.get_side_draw_offer(side))
+            if let Status::Finished { .. } = self.info.status {
+                return Err(Error::GameAlreadyOver);
+            }
+
+            self.env().emit_event(Adjudicated {
+                winner: None,
+                void: true,
+            });
+
+            self.refund_game(GameOverReason::Void)
         }
 
-        /// Resigns the game
+        /// Pauses the clocks if `paused` is true, resumes them otherwise
+        ///
+        /// No actions other than arbiters can be taken while the clocks are paused
         #[ink(message)]
-        pub fn resign(&mut self) -> Result<()> {
-            let next_side = self.game.side_next_in_turn();
+        pub fn pause_clocks(&mut self, paused: bool) -> Result<()> {
+            self.ensure_arbiter()?;
 
-            if !self.side_belongs_to_caller(next_side) {
-                return Err(Error::InvalidCaller);
+            self.ensure_in_progress()?;
+
+            if self.info.paused == paused {
+                return Ok(());
             }
 
-            self.terminate_game(Some(next_side.flip()), GameOverReason::Resignation)
-        }
+            if paused {
+                self.info.paused_block = self.env().block_number();
+                self.info.paused_timestamp = self.env().block_timestamp();
+            } else {
+                // Shift last move by the pause so that time spent before it still counts
+                self.info.last_move_block += self.env().block_number() - self.info.paused_block;
+                self.info.last_move_timestamp +=
+                    self.env().block_timestamp() - self.info.paused_timestamp;
 
-        fn terminate_game(&mut self, winner: Option<Side>, reason: GameOverReason) -> Result<()> {
-            let balance = self.env().balance();
-            let fee = balance / BALANCE_DISTRIBUTION_RATIO;
-            let pot = balance - fee;
+                let pause = self.env().block_number() - self.info.paused_block;
 
-            match winner {
-                Some(Side::White) => self.env().transfer(self.info.white_account, pot)?,
-                Some(Side::Black) => self.env().transfer(self.info.black_account, pot)?,
-                None => {
-                    let split = pot / 2;
-                    self.env().transfer(self.info.white_account, split)?;
-                    self.env().transfer(self.info.black_account, split)?;
+                // Dispute period of a pending checkpoint does not pass while paused
+                if let Some(deadline) = self.info.checkpoint_deadline {
+                    self.info.checkpoint_deadline = Some(deadline + pause);
+                }
+
+                for side in [Side::White, Side::Black].iter() {
+                    if let Some(mut commitment) = self.get_side_commitment(*side) {
+                        commitment.block += pause;
+                        self.set_side_commitment(*side, Some(commitment));
+                    }
                 }
             }
 
-            let winner = winner.map(|side| String::from(side.as_str()));
-            let reason = String::from(reason.as_str());
+            self.info.paused = paused;
 
-            self.env().emit_event(GameOver { winner, reason });
-            self.env().terminate_contract(FEE_BENEFICIARY.into())
+            self.env().emit_event(ClockPause { paused });
+
+            Ok(())
         }
 
-        fn terminate_game_out_of_blocks(&mut self, out_of_blocks_side: Side) -> Result<()> {
-            let opponent_side = out_of_blocks_side.flip();
+        /// Returns true if the clocks are paused by the arbiter
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.info.paused
+        }
 
-            if self.game.side_has_sufficient_mating_material(opponent_side) {
-                return self.terminate_game(Some(opponent_side), GameOverReason::Abandonment);
+        /// Adds `amount` blocks (or milliseconds, see `clock_unit`) to the `side` players clock
+        #[ink(message)]
+        pub fn add_time(&mut self, side: Side, amount: u32) -> Result<()> {
+            self.ensure_arbiter()?;
+
+            self.ensure_in_progress()?;
+
+            let blocks_left = match side {
+                Side::White => &mut self.info.white_blocks_left,
+                Side::Black => &mut self.info.black_blocks_left,
+            };
+
+            *blocks_left = blocks_left.checked_add(amount).ok_or(Error::TimeOverflow)?;
+
+            self.env().emit_event(TimeAdded { side, amount });
+
+            Ok(())
+        }
+
+        /// Removes contract of a finished game, sending the remaining fee to its beneficiary
+        ///
+        /// Only the beneficiary can collect the fee, once `FEE_GRACE_PERIOD` blocks
+        /// have passed since the end of the game
+        #[ink(message)]
+        pub fn collect_fee(&mut self) -> Result<()> {
+            self.ensure_finished()?;
+
+            if self.env().caller() != AccountId::from(FEE_BENEFICIARY) {
+                return Err(Error::InvalidCaller);
+            }
+
+            if self.env().block_number() < self.info.finished_block + FEE_GRACE_PERIOD {
+                return Err(Error::GracePeriodNotOver);
             }
 
-            return self.terminate_game(None, GameOverReason::Abandonment);
+            self.env().terminate_contract(FEE_BENEFICIARY.into())
+        }
+
+        /// Returns all moves made so far encoded by `Mov::encode`, each paired
+        /// with number of the block it was made in
+        #[ink(message)]
+        pub fn moves(&self) -> alloc::vec::Vec<(u16, BlockNumber)> {
+            self.history.iter().map(|ply| (ply.mov, ply.block)).collect()
         }
 
-        fn max_repetition(&self) -> u32 {
-            *self
-                .zobrist
-                .iter()
-                .fold(HashMap::<ZobristHash, u32>::new(), |mut map, zhash| {
-                    *map.entry(*zhash).or_insert(0) += 1;
-                    map
-                })
+        /// Returns number of plies made so far
+        #[ink(message)]
+        pub fn move_count(&self) -> u32 {
+            self.history.len()
+        }
+
+        /// Returns FEN string representation of the board after `ply` plies were made
+        #[ink(message)]
+        pub fn position_at(&self, ply: u32) -> Result<String> {
+            Ok(self.replay(ply)?.fen()?)
+        }
+
+        /// Returns PGN representation of the game
+        #[ink(message)]
+        pub fn pgn(&self) -> Result<String> {
+            let mut game = Game::new(self.start_fen.as_str())?;
+            let mut pgn = String::new();
+
+            writeln!(&mut pgn, "[Event \"DotChess\"]")?;
+
+            if self.start_fen.as_str() != Game::FEN_NEW_GAME {
+                writeln!(&mut pgn, "[SetUp \"1\"]")?;
+                writeln!(&mut pgn, "[FEN \"{}\"]", self.start_fen.as_str())?;
+            }
+
+            writeln!(&mut pgn, "[Result \"*\"]\n")?;
+
+            for (index, ply) in self.history.iter().enumerate() {
+                let mov = Mov::decode(ply.mov)?;
+
+                match game.side_next_in_turn() {
+                    Side::White => write!(&mut pgn, "{}. ", game.fullmove_number())?,
+                    Side::Black if index == 0 => {
+                        write!(&mut pgn, "{}... ", game.fullmove_number())?
+                    }
+                    Side::Black => {}
+                }
+
+                write!(&mut pgn, "{} ", game.san(&mov)?)?;
+
+                game = game.make_move(&mov)?;
+            }
+
+            write!(&mut pgn, "*")?;
+
+            Ok(pgn)
+        }
+
+        /// Same as `make_move` with `mov` given by its string representation
+        #[ink(message)]
+        pub fn make_move_str(&mut self, mov: String) -> Result<()> {
+            self.make_move(mov.as_str().try_into()?)
+        }
+
+        /// Same as `claim_draw` with `mov` given by its string representation
+        #[ink(message)]
+        pub fn claim_draw_str(&mut self, mov: Option<String>) -> Result<()> {
+            let mov = match mov {
+                Some(mov) => Some(mov.as_str().try_into()?),
+                None => None,
+            };
+
+            self.claim_draw(mov)
+        }
+
+        /// Same as `blocks_left` with `side` {"white","black"} given as a string
+        #[ink(message)]
+        pub fn blocks_left_str(&self, side: String) -> Result<u32> {
+            Ok(self.blocks_left(Side::from_str(side)?))
+        }
+
+        /// Same as `side_draw_offer` with `side` {"white","black"} given as a string
+        #[ink(message)]
+        pub fn side_draw_offer_str(&self, side: String) -> Result<bool> {
+            Ok(self.side_draw_offer(Side::from_str(side)?))
+        }
+
+        /// Returns side {"white","black"} next in turn
+        #[ink(message)]
+        pub fn side_to_move_str(&self) -> String {
+            String::from(self.side_to_move().as_str())
+        }
+
+        /// Returns legal moves of the side next in turn formatted like `make_move_str` input
+        #[ink(message)]
+        pub fn legal_moves_str(&self) -> alloc::vec::Vec<String> {
+            self.legal_moves().into_iter().map(Into::<String>::into).collect()
+        }
+
+        /// Returns legal moves from `square` {"a1",...,"h8"} formatted like `make_move_str` input
+        #[ink(message)]
+        pub fn legal_moves_from_str(&self, square: String) -> Result<alloc::vec::Vec<String>> {
+            let square: Square = square.as_str().try_into()?;
+
+            Ok(self
+                .legal_moves_from(square)
                 .into_iter()
-                .max_by_key(|(_, v)| *v)
-                .map(|(_, v)| v)
-                .unwrap()
+                .map(Into::<String>::into)
+                .collect())
         }
 
-        fn get_side_draw_offer(&self, side: Side) -> bool {
-            match side {
-                Side::White => self.info.white_draw_offer,
-                Side::Black => self.info.black_draw_offer,
+        /// Makes a move `mov`
+        ///
+        /// String representation of `mov` accepted by `Mov::try_from` is formatted as
+        /// [FROM FILE][FROM RANK][TO FILE][TO RANK][PROMO PIECE?]
+        ///
+        /// Codes for promotion pieces are:
+        ///   - N = knight
+        ///   - B = bishop
+        ///   - R = rook
+        ///   - Q = queen
+        ///
+        /// Example move for a pawn-to-queen promotion move from A7 to A8 is denoted as a7a8Q
+        #[ink(message)]
+        pub fn make_move(&mut self, mov: Mov) -> Result<()> {
+            self.ensure_active()?;
+
+            self.ensure_channel_closed()?;
+
+            let us_side = self.game.side_next_in_turn();
+
+            if !self.side_acts_for_caller(us_side, Action::MakeMove) {
+                return Err(Error::InvalidCaller);
+            }
+
+            if self.side_blocks_left(us_side) == 0 {
+                return self.terminate_game_out_of_blocks(us_side);
             }
+
+            self.play_move(us_side, mov, true)
         }
 
-        fn set_side_draw_offer(&mut self, side: Side, offer: bool) -> () {
-            match side {
-                Side::White => self.info.white_draw_offer = offer,
-                Side::Black => self.info.black_draw_offer = offer,
+        /// Reports that next side has abandoned the match
+        #[ink(message)]
+        pub fn report_abandonment(&mut self) -> Result<()> {
+            self.report_timeout(self.game.side_next_in_turn())
+        }
+
+        /// Reports that `side` has run out of time, ending the game
+        ///
+        /// Caller who is neither a player nor a delegate of one is paid the reporter
+        /// bounty, which is taken from the fee and capped at it
+        #[ink(message)]
+        pub fn report_timeout(&mut self, side: Side) -> Result<()> {
+            self.ensure_active()?;
+
+            if self.side_blocks_left(side) != 0 {
+                return Err(Error::NotOutOfTime);
+            }
+
+            let reporter = self.env().caller();
+            let is_third_party = [Side::White, Side::Black].iter().all(|side| {
+                self.side_account(*side) != reporter
+                    && self
+                        .delegate_of(*side)
+                        .map_or(true, |delegate| delegate.account != reporter)
+            });
+
+            if !is_third_party || self.info.reporter_bounty == 0 {
+                return self.terminate_game_out_of_blocks(side);
             }
+
+            let winner = self.out_of_blocks_winner(side);
+
+            self.finish_game(winner, GameOverReason::Abandonment, Some(reporter))
         }
 
-        fn side_account(&self, side: Side) -> AccountId {
-            match side {
-                Side::White => self.info.white_account,
-                Side::Black => self.info.black_account,
+        /// Returns bounty paid to a third party reporting a timeout
+        #[ink(message)]
+        pub fn reporter_bounty(&self) -> Balance {
+            self.info.reporter_bounty
+        }
+
+        /// If `offer` is true, proposes draw to the opposing player,
+        /// otherwise withdraws any previous draw offers
+        ///
+        /// Either player may offer a draw at any time, accepting the offer of the opponent
+        /// if there is one. The offer expires when the opponent makes a move. A side can
+        /// offer a draw once per `DRAW_OFFER_INTERVAL` plies.
+        #[ink(message)]
+        pub fn offer_draw(&mut self, offer: bool) -> Result<()> {
+            self.ensure_active()?;
+
+            self.ensure_channel_closed()?;
+
+            let side = self
+                .acting_side(Action::OfferDraw)
+                .ok_or(Error::InvalidCaller)?;
+
+            let next_side = self.game.side_next_in_turn();
+
+            if self.side_blocks_left(next_side) == 0 {
+                return self.terminate_game_out_of_blocks(next_side);
             }
+
+            self.ensure_draw_offer_allowed(side, offer)?;
+
+            self.update_draw_offer(side, offer)
         }
 
-        fn side_has_next_turn(&self, side: Side) -> bool {
-            side as u8 == self.game.side_next_in_turn() as u8
+        /// Declines draw offer of the opponent
+        #[ink(message)]
+        pub fn decline_draw(&mut self) -> Result<()> {
+            self.ensure_active()?;
+
+            self.ensure_channel_closed()?;
+
+            if self.acting_side(Action::OfferDraw).is_none() {
+                return Err(Error::InvalidCaller);
+            }
+
+            let offering_side = [Side::White, Side::Black]
+                .iter()
+                .copied()
+                .find(|side| {
+                    self.get_side_draw_offer(*side)
+                        && self.side_acts_for_caller(side.flip(), Action::OfferDraw)
+                })
+                .ok_or(Error::NoDrawOffer)?;
+
+            self.set_side_draw_offer(offering_side, false);
+
+            self.env().emit_event(DrawOfferUpdate {
+                side: offering_side,
+                offer: false,
+                change: DrawOfferChange::Declined,
+            });
+
+            Ok(())
         }
 
-        fn side_belongs_to_caller(&self, side: Side) -> bool {
-            self.env().caller() == self.side_account(side)
+        /// Returns true if `side` proposes a draw, false otherwise
+        #[ink(message)]
+        pub fn side_draw_offer(&self, side: Side) -> bool {
+            self.get_side_draw_offer(side)
         }
 
-        fn side_blocks_left(&self, side: Side) -> u32 {
-            let blocks_left = match side {
-                Side::White => self.info.white_blocks_left,
-                Side::Black => self.info.black_blocks_left,
+        /// Commits the caller to a move or draw offer by its `hash`, see
+        /// `move_commitment_hash` and `draw_offer_commitment_hash`
+        ///
+        /// The side to move can commit to either, its opponent only to a draw offer.
+        /// The commitment is revealed by `reveal_move` or `reveal_draw_offer` in one of
+        /// the next `REVEAL_WINDOW` blocks, a commitment which is not revealed in time
+        /// is void. Clock keeps running until the reveal. Committing again replaces
+        /// the pending commitment of the side.
+        #[ink(message)]
+        pub fn commit(&mut self, hash: [u8; 32]) -> Result<()> {
+            self.ensure_active()?;
+
+            self.ensure_channel_closed()?;
+
+            let next_side = self.game.side_next_in_turn();
+
+            let side = if self.side_acts_for_caller(next_side, Action::MakeMove)
+                || self.side_acts_for_caller(next_side, Action::OfferDraw)
+            {
+                next_side
+            } else if self.side_acts_for_caller(next_side.flip(), Action::OfferDraw) {
+                next_side.flip()
+            } else {
+                return Err(Error::InvalidCaller);
             };
 
-            if self.side_has_next_turn(side) {
-                let block_diff = self.block_diff_since_last_move();
-                let blocks_left_plus_1 = blocks_left + 1;
+            if self.side_blocks_left(next_side) == 0 {
+                return self.terminate_game_out_of_blocks(next_side);
+            }
+
+            let block = self.env().block_number();
+            let ply = self.history.len();
 
-                if blocks_left_plus_1 < block_diff {
-                    return 0;
-                }
+            self.set_side_commitment(side, Some(Commitment { ply, hash, block }));
 
-                return blocks_left_plus_1 - block_diff;
+            self.env().emit_event(Committed {
+                side,
+                ply,
+                reveal_deadline: block + REVEAL_WINDOW,
+            });
+
+            Ok(())
+        }
+
+        /// Returns commitment of `side` which can still be revealed
+        #[ink(message)]
+        pub fn commitment(&self, side: Side) -> Option<Commitment> {
+            self.pending_commitment(side)
+        }
+
+        /// Makes committed `mov`, see `commit`
+        #[ink(message)]
+        pub fn reveal_move(&mut self, mov: Mov, salt: [u8; 32]) -> Result<()> {
+            self.ensure_active()?;
+
+            self.ensure_channel_closed()?;
+
+            let us_side = self.game.side_next_in_turn();
+
+            if !self.side_acts_for_caller(us_side, Action::MakeMove) {
+                return Err(Error::InvalidCaller);
             }
 
-            blocks_left
+            self.ensure_commitment(us_side, self.move_commitment_hash(mov, salt))?;
+
+            // Validate move before the commitment is released
+            self.game.make_move(&mov)?;
+
+            self.set_side_commitment(us_side, None);
+
+            if self.side_blocks_left(us_side) == 0 {
+                return self.terminate_game_out_of_blocks(us_side);
+            }
+
+            self.play_move(us_side, mov, true)
         }
 
-        fn block_diff_since_last_move(&self) -> u32 {
-            self.env().block_number() - self.info.last_move_block
+        /// Makes or withdraws committed draw offer, see `commit` and `offer_draw`
+        #[ink(message)]
+        pub fn reveal_draw_offer(&mut self, offer: bool, salt: [u8; 32]) -> Result<()> {
+            self.ensure_active()?;
+
+            self.ensure_channel_closed()?;
+
+            let next_side = self.game.side_next_in_turn();
+            let side = self.acting_side(Action::OfferDraw).ok_or(Error::InvalidCaller)?;
+
+            self.ensure_commitment(side, self.draw_offer_commitment_hash(offer, salt))?;
+            self.ensure_draw_offer_allowed(side, offer)?;
+
+            self.set_side_commitment(side, None);
+
+            if self.side_blocks_left(next_side) == 0 {
+                return self.terminate_game_out_of_blocks(next_side);
+            }
+
+            self.update_draw_offer(side, offer)
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-        use ink_env::AccountId;
-        use ink_lang as ink;
+        /// Returns hash to `commit` to making `mov` in the current position
+        #[ink(message)]
+        pub fn move_commitment_hash(&self, mov: Mov, salt: [u8; 32]) -> [u8; 32] {
+            self.env().hash_encoded::<Blake2x256, _>(&(
+                b"move-commit",
+                self.env().account_id(),
+                self.history.len(),
+                mov,
+                salt,
+            ))
+        }
 
-        #[ink::test]
-        fn make_move() {
-            let white = AccountId::from([0x01; 32]);
-            let black = AccountId::from([0x01; 32]);
+        /// Returns hash to `commit` to making (or withdrawing) a draw offer in the
+        /// current position
+        #[ink(message)]
+        pub fn draw_offer_commitment_hash(&self, offer: bool, salt: [u8; 32]) -> [u8; 32] {
+            self.env().hash_encoded::<Blake2x256, _>(&(
+                b"draw-commit",
+                self.env().account_id(),
+                self.history.len(),
+                offer,
+                salt,
+            ))
+        }
 
-            let mut chess = DotChess::new(white, black, 1, 1);
+        /// Resigns the game
+        #[ink(message)]
+        pub fn resign(&mut self) -> Result<()> {
+            self.ensure_active()?;
+
+            let next_side = self.game.side_next_in_turn();
+
+            if !self.side_belongs_to_caller(next_side) {
+                return Err(Error::InvalidCaller);
+            }
+
+            self.terminate_game(Some(next_side.flip()), GameOverReason::Resignation)
+        }
+
+        /// Claims a draw by threefold repetition or by the fifty move rule
+        ///
+        /// If `mov` is given, the claim is made on the position after that move is made,
+        /// otherwise on the current position. Move which mates or stalemates the opponent
+        /// ends the game by itself, so it cannot be used to claim a draw.
+        #[ink(message)]
+        pub fn claim_draw(&mut self, mov: Option<Mov>) -> Result<()> {
+            self.ensure_active()?;
+
+            self.ensure_channel_closed()?;
+
+            let next_side = self.game.side_next_in_turn();
+
+            if !self.side_acts_for_caller(next_side, Action::ClaimDraw) {
+                return Err(Error::InvalidCaller);
+            }
+
+            if self.side_blocks_left(next_side) == 0 {
+                return self.terminate_game_out_of_blocks(next_side);
+            }
+
+            let (repetitions, halfmove_clock) = match mov {
+                Some(mov) => {
+                    let game_new = self.game.make_move(&mov)?;
+
+                    if !game_new.has_legal_moves() {
+                        return Err(Error::DrawNotClaimable);
+                    }
+
+                    let repetitions = if game_new.halfmove_clock() == 0 {
+                        1
+                    } else {
+                        self.repetition_count(game_new.zobrist()) + 1
+                    };
+
+                    (repetitions, game_new.halfmove_clock())
+                }
+                None => (
+                    self.repetition_count(self.game.zobrist()),
+                    self.game.halfmove_clock(),
+                ),
+            };
+
+            if repetitions >= 3 {
+                return self.terminate_game(None, GameOverReason::ThreefoldRepetition);
+            }
 
-            chess.make_move("d2d3".to_string()).unwrap();
-            chess.make_move("g7g6".to_string()).unwrap();
+            if halfmove_clock >= 100 {
+                return self.terminate_game(None, GameOverReason::FiftyMoveRule);
+            }
+
+            Err(Error::DrawNotClaimable)
+        }
+
+        /// Requests the opponent to take back last `n_plies` plies
+        ///
+        /// The request expires when either side makes a move
+        #[ink(message)]
+        pub fn request_takeback(&mut self, n_plies: u32) -> Result<()> {
+            self.ensure_active()?;
+
+            self.ensure_channel_closed()?;
+
+            let side = self
+                .acting_side(Action::Takeback)
+                .ok_or(Error::InvalidCaller)?;
+
+            if n_plies == 0 || n_plies > self.history.len() {
+                return Err(Error::InvalidPly);
+            }
+
+            self.info.takeback_side = Some(side);
+            self.info.takeback_ply = self.history.len() - n_plies;
+
+            self.env().emit_event(TakebackRequest { side, n_plies });
+
+            Ok(())
+        }
+
+        /// Accepts opponents takeback request
+        #[ink(message)]
+        pub fn accept_takeback(&mut self) -> Result<()> {
+            self.ensure_active()?;
+
+            self.ensure_channel_closed()?;
+
+            let requesting_side = self.pending_takeback_side()?;
+
+            if !self.side_acts_for_caller(requesting_side.flip(), Action::Takeback) {
+                return Err(Error::InvalidCaller);
+            }
+
+            let next_side = self.game.side_next_in_turn();
+
+            if self.side_blocks_left(next_side) == 0 {
+                return self.terminate_game_out_of_blocks(next_side);
+            }
+
+            let ply = self.info.takeback_ply;
+            let n_plies = self.history.len() - ply;
+
+            self.take_back_to(ply)?;
+
+            self.info.takeback_side = None;
+            self.info.takeback_ply = 0;
+
+            self.env().emit_event(TakebackAccept {
+                side: requesting_side,
+                n_plies,
+                fen: self.game.fen()?,
+            });
+
+            Ok(())
+        }
+
+        /// Declines opponents takeback request
+        #[ink(message)]
+        pub fn decline_takeback(&mut self) -> Result<()> {
+            let requesting_side = self.pending_takeback_side()?;
+
+            if !self.side_acts_for_caller(requesting_side.flip(), Action::Takeback) {
+                return Err(Error::InvalidCaller);
+            }
+
+            self.clear_takeback_request(false);
+
+            Ok(())
+        }
+
+        /// Authorizes `delegate` to perform actions allowed by `permissions` on behalf
+        /// of the caller until the `expires` block, replacing any previous delegate
+        #[ink(message)]
+        pub fn authorize_delegate(
+            &mut self,
+            delegate: AccountId,
+            expires: BlockNumber,
+            permissions: Permissions,
+        ) -> Result<()> {
+            if expires <= self.env().block_number() {
+                return Err(Error::InvalidExpiry);
+            }
+
+            self.set_caller_delegate(Some(Delegate {
+                account: delegate,
+                expires,
+                permissions,
+            }))
+        }
+
+        /// Revokes delegate of the caller
+        #[ink(message)]
+        pub fn revoke_delegate(&mut self) -> Result<()> {
+            self.set_caller_delegate(None)
+        }
+
+        /// Returns delegate authorized by the `side` player, if it has not expired yet
+        #[ink(message)]
+        pub fn delegate_of(&self, side: Side) -> Option<Delegate> {
+            let delegate = match side {
+                Side::White => self.info.white_delegate,
+                Side::Black => self.info.black_delegate,
+            };
+
+            delegate.filter(|delegate| self.env().block_number() < delegate.expires)
+        }
+
+        fn set_caller_delegate(&mut self, delegate: Option<Delegate>) -> Result<()> {
+            let caller = self.env().caller();
+            let mut authorized = false;
+
+            for side in [Side::White, Side::Black].iter().copied() {
+                if self.side_account(side) != caller {
+                    continue;
+                }
+
+                match side {
+                    Side::White => self.info.white_delegate = delegate,
+                    Side::Black => self.info.black_delegate = delegate,
+                }
+
+                self.env().emit_event(DelegateUpdate {
+                    side,
+                    delegate: delegate.map(|delegate| delegate.account),
+                    expires: delegate.map(|delegate| delegate.expires).unwrap_or(0),
+                });
+
+                authorized = true;
+            }
+
+            if authorized {
+                Ok(())
+            } else {
+                Err(Error::InvalidCaller)
+            }
+        }
+
+        /// Agrees to continue the game off-chain, clocks stop once both players agree
+        ///
+        /// Players then exchange `SignedMove`s and either `settle` the result or
+        /// `checkpoint` the moves, which restarts the clock of the side to move. Moves,
+        /// draw offers, draw claims and takebacks are rejected on-chain until then.
+        #[ink(message)]
+        pub fn open_channel(&mut self) -> Result<()> {
+            self.ensure_active()?;
+
+            let next_side = self.game.side_next_in_turn();
+
+            if self.side_blocks_left(next_side) == 0 {
+                return self.terminate_game_out_of_blocks(next_side);
+            }
+
+            let caller = self.env().caller();
+
+            if caller != self.info.white_account && caller != self.info.black_account {
+                return Err(Error::InvalidCaller);
+            }
+
+            if caller == self.info.white_account {
+                self.info.white_channel = true;
+            }
+
+            if caller == self.info.black_account {
+                self.info.black_channel = true;
+            }
+
+            if self.channel_open() {
+                self.env().emit_event(ChannelUpdate { open: true });
+            }
+
+            Ok(())
+        }
+
+        /// Returns true if the game is being played off-chain
+        #[ink(message)]
+        pub fn channel_open(&self) -> bool {
+            self.info.white_channel && self.info.black_channel
+        }
+
+        /// Submits `moves` played off-chain since the last on-chain move
+        ///
+        /// Every move is verified against the signature of the player who made it
+        /// and nothing is submitted unless all of them are valid. Until `DISPUTE_PERIOD`
+        /// blocks pass, either player can replace the checkpoint by a longer sequence
+        /// extending it, then `apply_checkpoint` replays the longest one. Sequence which
+        /// diverges from the pending one proves that the player who made the first
+        /// differing move signed two moves for the same ply, that player loses the game.
+        #[ink(message)]
+        pub fn checkpoint(&mut self, moves: alloc::vec::Vec<SignedMove>) -> Result<()> {
+            self.ensure_active()?;
+
+            if !self.channel_open() {
+                return Err(Error::ChannelNotOpen);
+            }
+
+            let side = self.caller_side().ok_or(Error::InvalidCaller)?;
+            let block = self.env().block_number();
+
+            if let Some(deadline) = self.info.checkpoint_deadline {
+                if block > deadline {
+                    return Err(Error::DisputePeriodOver);
+                }
+            }
+
+            let mut game: Option<Game> = None;
+
+            for (index, signed) in moves.iter().enumerate() {
+                if signed.ply != self.history.len() + index as u32 {
+                    return Err(Error::InvalidPly);
+                }
+
+                let current = game.as_ref().unwrap_or(&*self.game);
+                let side = current.side_next_in_turn();
+                let hash = self.signed_move_hash(signed.ply, signed.mov, signed.zobrist);
+
+                if self.signer(&signed.signature, &hash)? != self.side_account(side) {
+                    return Err(Error::InvalidSignature);
+                }
+
+                let game_new = current.make_move(&signed.mov)?;
+
+                if game_new.zobrist() != signed.zobrist {
+                    return Err(Error::PositionMismatch);
+                }
+
+                game = Some(game_new);
+            }
+
+            if self.info.checkpoint_deadline.is_some() {
+                let divergence = self
+                    .checkpoint
+                    .iter()
+                    .zip(moves.iter())
+                    .position(|(pending, signed)| *pending != signed.mov.encode());
+
+                if let Some(index) = divergence {
+                    // Both moves of the first differing ply were signed by the side making it
+                    let next_side = self.game.side_next_in_turn();
+                    let equivocating_side = match index % 2 {
+                        0 => next_side,
+                        _ => next_side.flip(),
+                    };
+
+                    self.close_channel();
+                    self.env().emit_event(ChannelUpdate { open: false });
+
+                    return self.terminate_game(
+                        Some(equivocating_side.flip()),
+                        GameOverReason::Equivocation,
+                    );
+                }
+
+                if moves.len() as u32 <= self.checkpoint.len() {
+                    return Err(Error::CheckpointNotLonger);
+                }
+            }
+
+            self.checkpoint.clear();
+
+            for signed in moves.iter() {
+                self.checkpoint.push(signed.mov.encode());
+            }
+
+            let dispute_deadline = block + DISPUTE_PERIOD;
+
+            self.info.checkpoint_deadline = Some(dispute_deadline);
+
+            self.env().emit_event(CheckpointSubmitted {
+                side,
+                plies: moves.len() as u32,
+                dispute_deadline,
+            });
+
+            Ok(())
+        }
+
+        /// Replays the pending checkpoint once its dispute period is over and closes
+        /// the channel, the side to move then has to respond on-chain before its clock
+        /// runs out
+        ///
+        /// Clocks were stopped while playing off-chain, so the plies are applied
+        /// with the clocks both players had when the channel opened
+        #[ink(message)]
+        pub fn apply_checkpoint(&mut self) -> Result<()> {
+            self.ensure_active()?;
+
+            let deadline = self.info.checkpoint_deadline.ok_or(Error::NoCheckpoint)?;
+
+            if self.env().block_number() <= deadline {
+                return Err(Error::DisputePeriodNotOver);
+            }
+
+            let moves = self
+                .checkpoint
+                .iter()
+                .map(|mov| Mov::decode(*mov))
+                .collect::<Result<alloc::vec::Vec<Mov>>>()?;
+
+            self.close_channel();
+            self.info.last_move_block = self.env().block_number();
+            self.info.last_move_timestamp = self.env().block_timestamp();
+
+            self.env().emit_event(ChannelUpdate { open: false });
+
+            for mov in moves {
+                self.play_move(self.game.side_next_in_turn(), mov, false)?;
+
+                if self.info.status != Status::Active {
+                    break;
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Returns number of plies of the pending checkpoint and the block until which
+        /// it can be replaced by a longer one, if there is a pending checkpoint
+        #[ink(message)]
+        pub fn pending_checkpoint(&self) -> Option<(u32, BlockNumber)> {
+            self.info
+                .checkpoint_deadline
+                .map(|deadline| (self.checkpoint.len(), deadline))
+        }
+
+        /// Finishes the game with the result both players signed off-chain
+        /// over `settlement_hash`
+        #[ink(message)]
+        pub fn settle(
+            &mut self,
+            winner: Option<Side>,
+            white_signature: Signature,
+            black_signature: Signature,
+        ) -> Result<()> {
+            self.ensure_active()?;
+
+            let hash = self.settlement_hash(winner);
+
+            if self.signer(&white_signature, &hash)? != self.info.white_account
+                || self.signer(&black_signature, &hash)? != self.info.black_account
+            {
+                return Err(Error::InvalidSignature);
+            }
+
+            self.terminate_game(winner, GameOverReason::Settlement)
+        }
+
+        /// Returns hash players sign off-chain to confirm `mov` was made as
+        /// the `ply`th ply (zero based) of this game resulting in position `zobrist`
+        #[ink(message)]
+        pub fn signed_move_hash(&self, ply: u32, mov: Mov, zobrist: ZobristHash) -> [u8; 32] {
+            self.env().hash_encoded::<Blake2x256, _>(&(
+                b"move",
+                self.env().account_id(),
+                ply,
+                mov,
+                zobrist,
+            ))
+        }
+
+        /// Returns hash players sign off-chain to agree on the result of this game
+        #[ink(message)]
+        pub fn settlement_hash(&self, winner: Option<Side>) -> [u8; 32] {
+            self.env().hash_encoded::<Blake2x256, _>(&(
+                b"settle",
+                self.env().account_id(),
+                winner,
+            ))
+        }
+
+        /// Returns account of the ECDSA `signature` signer, the account is
+        /// Blake2 hash of the compressed public key
+        fn signer(&self, signature: &Signature, hash: &[u8; 32]) -> Result<AccountId> {
+            let public_key = signature.recover(hash)?;
+
+            Ok(self.env().hash_bytes::<Blake2x256>(&public_key).into())
+        }
+
+        /// Returns the commitment of `side` if it was made for the current ply and
+        /// its reveal window has not passed
+        fn pending_commitment(&self, side: Side) -> Option<Commitment> {
+            let commitment = self.get_side_commitment(side)?;
+
+            // Reveal window does not pass while the clocks are paused
+            let block = match self.info.paused {
+                true => self.info.paused_block,
+                false => self.env().block_number(),
+            };
+
+            if commitment.ply != self.history.len() || block > commitment.block + REVEAL_WINDOW {
+                return None;
+            }
+
+            Some(commitment)
+        }
+
+        /// Checks that the pending commitment of `side` can be revealed and matches `hash`
+        fn ensure_commitment(&self, side: Side, hash: [u8; 32]) -> Result<()> {
+            let commitment = match self.get_side_commitment(side) {
+                Some(commitment) if commitment.ply == self.history.len() => commitment,
+                _ => return Err(Error::NoCommitment),
+            };
+
+            if self.env().block_number() <= commitment.block {
+                return Err(Error::RevealTooEarly);
+            }
+
+            if self.pending_commitment(side).is_none() {
+                return Err(Error::RevealWindowClosed);
+            }
+
+            if commitment.hash != hash {
+                return Err(Error::CommitmentMismatch);
+            }
+
+            Ok(())
+        }
+
+        /// Checks that `side` has not offered a draw within the last `DRAW_OFFER_INTERVAL`
+        /// plies, accepting and withdrawing offers is always allowed
+        fn ensure_draw_offer_allowed(&self, side: Side, offer: bool) -> Result<()> {
+            if !offer || self.get_side_draw_offer(side) || self.get_side_draw_offer(side.flip()) {
+                return Ok(());
+            }
+
+            match self.get_side_draw_offer_ply(side) {
+                Some(ply) if self.history.len() < ply + DRAW_OFFER_INTERVAL => {
+                    Err(Error::DrawOfferTooSoon)
+                }
+                _ => Ok(()),
+            }
+        }
+
+        /// Sets draw offer of `side`, agreeing to a draw if the opponent offers one
+        fn update_draw_offer(&mut self, side: Side, offer: bool) -> Result<()> {
+            if offer && self.get_side_draw_offer(side.flip()) {
+                return self.terminate_game(None, GameOverReason::DrawAgreement);
+            }
+
+            if offer == self.get_side_draw_offer(side) {
+                return Ok(());
+            }
+
+            let change = if offer {
+                self.set_side_draw_offer_ply(side, self.history.len());
+                DrawOfferChange::Offered
+            } else {
+                DrawOfferChange::Withdrawn
+            };
+
+            self.set_side_draw_offer(side, offer);
+
+            self.env().emit_event(DrawOfferUpdate {
+                side,
+                offer,
+                change,
+            });
+
+            Ok(())
+        }
+
+        fn pending_takeback_side(&self) -> Result<Side> {
+            self.info.takeback_side.ok_or(Error::NoTakebackRequest)
+        }
+
+        fn clear_takeback_request(&mut self, expired: bool) {
+            if let Some(side) = self.info.takeback_side.take() {
+                self.info.takeback_ply = 0;
+
+                self.env().emit_event(TakebackDecline { side, expired });
+            }
+        }
+
+        /// Returns game after first `ply` plies of the move history were made
+        fn replay(&self, ply: u32) -> Result<Game> {
+            if ply > self.history.len() {
+                return Err(Error::InvalidPly);
+            }
+
+            let mut game = Game::new(self.start_fen.as_str())?;
+
+            for index in 0..ply {
+                let mov = Mov::decode(self.history.get(index).unwrap().mov)?;
+                game = game.make_move(&mov)?;
+            }
+
+            Ok(game)
+        }
+
+        /// Reverts game, zobrist history, clocks and draw offers to the state
+        /// before `ply`th ply was made
+        fn take_back_to(&mut self, ply: u32) -> Result<()> {
+            let mut game = Game::new(self.start_fen.as_str())?;
+
+            self.zobrist.clear();
+            self.zobrist.push(game.zobrist());
+
+            for index in 0..ply {
+                let mov = Mov::decode(self.history.get(index).unwrap().mov)?;
+                game = game.make_move(&mov)?;
+
+                if game.halfmove_clock() == 0 {
+                    self.zobrist.clear();
+                }
+
+                self.zobrist.push(game.zobrist());
+            }
+
+            let entry = self.history.get(ply).unwrap();
+            self.info.white_blocks_left = entry.white_blocks_left;
+            self.info.black_blocks_left = entry.black_blocks_left;
+
+            while self.history.len() > ply {
+                self.history.pop();
+            }
+
+            self.game = Pack::new(game);
+            self.info.white_draw_offer = false;
+            self.info.black_draw_offer = false;
+            self.info.white_commitment = None;
+            self.info.black_commitment = None;
+            self.info.last_move_block = self.env().block_number();
+            self.info.last_move_timestamp = self.env().block_timestamp();
+
+            Ok(())
+        }
+
+        /// Makes `mov` for `us_side`, updates the clocks and emits `BoardUpdate`
+        /// Plays `mov` of `us_side`, the time it took is charged by the time control
+        /// only if the move is `timed`, moves played off-chain are not
+        fn play_move(&mut self, us_side: Side, mov: Mov, timed: bool) -> Result<()> {
+            // Make move
+            let game_new = self.game.make_move(&mov)?;
+
+            // Describe move (must go before updating game)
+            let san = self.game.san(&mov)?;
+            let (_, piece) = self.game.piece_at(mov.from()).ok_or(Error::EmptyOriginSquare)?;
+            let captured = self.game.captured_piece(&mov);
+
+            // If halfmove clock resets, clear zobrist history
+            if game_new.halfmove_clock() == 0 {
+                self.zobrist.clear();
+            }
+
+            // Add zobrist to history
+            self.zobrist.push(game_new.zobrist());
+
+            // Add move to history (must go before updating blocks left)
+            let block = self.env().block_number();
+
+            self.history.push(Ply {
+                mov: mov.encode(),
+                block,
+                white_blocks_left: self.info.white_blocks_left,
+                black_blocks_left: self.info.black_blocks_left,
+            });
+
+            // Takeback request expires, the requested plies no longer match
+            self.clear_takeback_request(true);
+
+            // Commitments made for this ply are spent or void
+            self.info.white_commitment = None;
+            self.info.black_commitment = None;
+
+            // Opponents draw offer expires
+            if self.get_side_draw_offer(us_side.flip()) {
+                self.set_side_draw_offer(us_side.flip(), false);
+
+                self.env().emit_event(DrawOfferUpdate {
+                    side: us_side.flip(),
+                    offer: false,
+                    change: DrawOfferChange::Expired,
+                });
+            }
+
+            // Update blocks left (must go before updating last move clock)
+            if timed {
+                let block_diff = self.clock_diff_since_last_move();
+                let move_number = self.game.fullmove_number();
+                let time_control = self.side_time_control(us_side);
+
+                let blocks_left_ref = match us_side {
+                    Side::White => &mut self.info.white_blocks_left,
+                    Side::Black => &mut self.info.black_blocks_left,
+                };
+
+                *blocks_left_ref = time_control.blocks_left_after_move(
+                    *blocks_left_ref,
+                    block_diff,
+                    move_number,
+                    us_side,
+                );
+            }
+
+            let last_side_blocks_left = match us_side {
+                Side::White => self.info.white_blocks_left,
+                Side::Black => self.info.black_blocks_left,
+            };
+
+            // Update game and last move clock
+            self.game = ink_storage::Pack::new(game_new);
+            self.info.last_move_block = self.env().block_number();
+            self.info.last_move_timestamp = self.env().block_timestamp();
+
+            let op_side = us_side.flip();
+            let is_check = self.game.is_check();
+            let has_legal_moves = self.game.has_legal_moves();
+
+            let next_move_deadline = self.clock_now() + self.side_blocks_left(op_side) as u64;
+
+            // Emit event
+            self.env().emit_event(BoardUpdate {
+                next_side: op_side,
+                clock_unit: self.info.clock_unit,
+                next_move_deadline,
+                last_move: mov,
+                last_side_time_left: last_side_blocks_left,
+                fen: self.game.fen()?,
+                ply: self.history.len(),
+                san,
+                flags: MoveFlags {
+                    capture: captured.is_some(),
+                    check: is_check,
+                    checkmate: is_check && !has_legal_moves,
+                },
+                piece,
+                captured,
+                zobrist: self.game.zobrist(),
+                white_time_left: self.side_blocks_left(Side::White),
+                black_time_left: self.side_blocks_left(Side::Black),
+            });
+
+            // Checkmate, stalemate or insufficient mating material?
+            if let Some((winner, reason)) = self.game.position_result() {
+                return self.terminate_game(winner, reason);
+            }
+
+            // Check if player has no blocks left after this move
+            if timed && self.side_blocks_left(us_side) == 0 {
+                return self.terminate_game_out_of_blocks(us_side);
+            }
+
+            // Check for fivefold repetition and seventy-five move rule
+            let repetitions = self.repetition_count(self.game.zobrist());
+
+            if let Some(reason) = self.game.forced_draw(repetitions) {
+                return self.terminate_game(None, reason);
+            }
+
+            Ok(())
+        }
+
+        fn terminate_game(&mut self, winner: Option<Side>, reason: GameOverReason) -> Result<()> {
+            self.finish_game(winner, reason, None)
+        }
+
+        /// Pays out the pot and ends the game, `reporter` of the game end is paid
+        /// the reporter bounty from the fee
+        fn finish_game(
+            &mut self,
+            winner: Option<Side>,
+            reason: GameOverReason,
+            reporter: Option<AccountId>,
+        ) -> Result<()> {
+            let balance = match self.info.token {
+                Some(_) => 2 * self.info.stake,
+                None => self.env().balance(),
+            };
+            let mut fee = balance / BALANCE_DISTRIBUTION_RATIO;
+            let pot = balance - fee;
+
+            if let Some(reporter) = reporter {
+                let bounty = core::cmp::min(self.info.reporter_bounty, fee);
+
+                fee -= bounty;
+
+                self.pay(reporter, bounty)?;
+
+                self.env().emit_event(TimeoutReported { reporter, bounty });
+            }
+
+            match winner {
+                Some(Side::White) => self.pay(self.info.white_account, pot)?,
+                Some(Side::Black) => self.pay(self.info.black_account, pot)?,
+                None => {
+                    let split = pot / 2;
+                    self.pay(self.info.white_account, split)?;
+                    self.pay(self.info.black_account, split)?;
+                }
+            }
+
+            // Token fee is paid out right away, native fee is left for `collect_fee`
+            if let Some(token) = self.info.token {
+                token::transfer(token, FEE_BENEFICIARY.into(), fee)?;
+            }
+
+            self.info.status = Status::Finished { winner, reason };
+            self.info.finished_block = self.env().block_number();
+
+            self.env().emit_event(GameOver {
+                winner,
+                reason,
+                handicap: self.info.handicap,
+            });
+
+            self.mint_collectibles(winner, reason);
+
+            Ok(())
+        }
+
+        /// Mints the finished game to its collection, if there is one. The winner gets
+        /// the collectible, in a draw both players get one. Aborted and voided games are
+        /// not minted as they end by `refund_game`.
+        ///
+        /// Collectible contains the move history, starting position and the result,
+        /// failing to mint it does not prevent the game from ending
+        fn mint_collectibles(&self, winner: Option<Side>, reason: GameOverReason) {
+            let collection = match self.info.collection {
+                Some(collection) => collection,
+                None => return,
+            };
+
+            let sides: &[Side] = match winner {
+                Some(Side::White) => &[Side::White],
+                Some(Side::Black) => &[Side::Black],
+                None => &[Side::White, Side::Black],
+            };
+
+            for side in sides.iter().copied() {
+                let owner = self.side_account(side);
+                let collectible = Collectible {
+                    game: self.env().account_id(),
+                    side,
+                    moves: self.history.iter().map(|ply| ply.mov).collect(),
+                    start_fen: String::from(self.start_fen.as_str()),
+                    winner,
+                    reason,
+                };
+
+                if collection::mint(collection, owner, &collectible).is_ok() {
+                    self.env().emit_event(CollectibleMinted { owner, collection });
+                }
+            }
+        }
+
+        /// Refunds deposited stakes and ends the game without a winner
+        ///
+        /// In games staked in native balance, balance left after the refunds, such as
+        /// the contract endowment, is split between the players except for the minimum
+        /// balance the contract has to keep
+        fn refund_game(&mut self, reason: GameOverReason) -> Result<()> {
+            let stake = self.info.stake;
+
+            if self.info.white_deposited {
+                self.pay(self.info.white_account, stake)?;
+            }
+
+            if self.info.black_deposited {
+                self.pay(self.info.black_account, stake)?;
+            }
+
+            if self.info.token.is_none() {
+                let split = self
+                    .env()
+                    .balance()
+                    .saturating_sub(self.env().minimum_balance())
+                    / 2;
+
+                if split > 0 {
+                    self.pay(self.info.white_account, split)?;
+                    self.pay(self.info.black_account, split)?;
+                }
+            }
+
+            self.info.status = Status::Finished {
+                winner: None,
+                reason,
+            };
+            self.info.finished_block = self.env().block_number();
+
+            self.env().emit_event(GameOver {
+                winner: None,
+                reason,
+                handicap: self.info.handicap,
+            });
+
+            Ok(())
+        }
+
+        /// Pays `amount` of the stake currency to `account`
+        fn pay(&mut self, account: AccountId, amount: Balance) -> Result<()> {
+            match self.info.token {
+                Some(token) => token::transfer(token, account, amount),
+                None => Ok(self.env().transfer(account, amount)?),
+            }
+        }
+
+        fn ensure_arbiter(&self) -> Result<()> {
+            match self.info.arbiter {
+                Some(arbiter) if arbiter == self.env().caller() => Ok(()),
+                _ => Err(Error::InvalidCaller),
+            }
+        }
+
+        fn ensure_funding(&self) -> Result<()> {
+            match self.info.status {
+                Status::Funding => Ok(()),
+                Status::Active => Err(Error::GameAlreadyFunded),
+                Status::Finished { .. } => Err(Error::GameAlreadyOver),
+            }
+        }
+
+        fn ensure_active(&self) -> Result<()> {
+            self.ensure_in_progress()?;
+
+            if self.info.paused {
+                return Err(Error::GamePaused);
+            }
+
+            Ok(())
+        }
+
+        /// Same as `ensure_active`, but allows paused clocks
+        fn ensure_in_progress(&self) -> Result<()> {
+            match self.info.status {
+                Status::Funding => Err(Error::GameNotFunded),
+                Status::Active => Ok(()),
+                Status::Finished { .. } => Err(Error::GameAlreadyOver),
+            }
+        }
+
+        fn ensure_finished(&self) -> Result<()> {
+            match self.info.status {
+                Status::Finished { .. } => Ok(()),
+                _ => Err(Error::GameNotOver),
+            }
+        }
+
+        fn ensure_channel_closed(&self) -> Result<()> {
+            if self.channel_open() {
+                return Err(Error::ChannelOpen);
+            }
+
+            Ok(())
+        }
+
+        /// Moves the game back on-chain, discarding the pending checkpoint
+        fn close_channel(&mut self) {
+            self.info.white_channel = false;
+            self.info.black_channel = false;
+            self.info.checkpoint_deadline = None;
+            self.checkpoint.clear();
+        }
+
+        fn terminate_game_out_of_blocks(&mut self, out_of_blocks_side: Side) -> Result<()> {
+            let winner = self.out_of_blocks_winner(out_of_blocks_side);
+
+            self.terminate_game(winner, GameOverReason::Abandonment)
+        }
+
+        /// Opponent of the side out of blocks wins unless it cannot mate
+        fn out_of_blocks_winner(&self, out_of_blocks_side: Side) -> Option<Side> {
+            let opponent_side = out_of_blocks_side.flip();
+
+            if self.game.side_has_sufficient_mating_material(opponent_side) {
+                return Some(opponent_side);
+            }
+
+            None
+        }
+
+        fn repetition_count(&self, zhash: ZobristHash) -> u32 {
+            self.zobrist.iter().filter(|z| **z == zhash).count() as u32
+        }
+
+        fn get_side_draw_offer(&self, side: Side) -> bool {
+            match side {
+                Side::White => self.info.white_draw_offer,
+                Side::Black => self.info.black_draw_offer,
+            }
+        }
+
+        fn set_side_draw_offer(&mut self, side: Side, offer: bool) -> () {
+            match side {
+                Side::White => self.info.white_draw_offer = offer,
+                Side::Black => self.info.black_draw_offer = offer,
+            }
+        }
+
+        fn get_side_draw_offer_ply(&self, side: Side) -> Option<u32> {
+            match side {
+                Side::White => self.info.white_draw_offer_ply,
+                Side::Black => self.info.black_draw_offer_ply,
+            }
+        }
+
+        fn set_side_draw_offer_ply(&mut self, side: Side, ply: u32) {
+            match side {
+                Side::White => self.info.white_draw_offer_ply = Some(ply),
+                Side::Black => self.info.black_draw_offer_ply = Some(ply),
+            }
+        }
+
+        fn get_side_commitment(&self, side: Side) -> Option<Commitment> {
+            match side {
+                Side::White => self.info.white_commitment,
+                Side::Black => self.info.black_commitment,
+            }
+        }
+
+        fn set_side_commitment(&mut self, side: Side, commitment: Option<Commitment>) {
+            match side {
+                Side::White => self.info.white_commitment = commitment,
+                Side::Black => self.info.black_commitment = commitment,
+            }
+        }
+
+        fn side_account(&self, side: Side) -> AccountId {
+            match side {
+                Side::White => self.info.white_account,
+                Side::Black => self.info.black_account,
+            }
+        }
+
+        fn side_has_next_turn(&self, side: Side) -> bool {
+            side as u8 == self.game.side_next_in_turn() as u8
+        }
+
+        fn side_belongs_to_caller(&self, side: Side) -> bool {
+            self.env().caller() == self.side_account(side)
+        }
+
+        /// Returns true if caller is the `side` player, or their delegate allowed to perform `action`
+        fn side_acts_for_caller(&self, side: Side, action: Action) -> bool {
+            if self.side_belongs_to_caller(side) {
+                return true;
+            }
+
+            match self.delegate_of(side) {
+                Some(delegate) => {
+                    delegate.account == self.env().caller() && delegate.permissions.allows(action)
+                }
+                None => false,
+            }
+        }
+
+        /// Returns side the caller may perform `action` for, preferring the side next in turn
+        fn acting_side(&self, action: Action) -> Option<Side> {
+            let next_side = self.game.side_next_in_turn();
+
+            if self.side_acts_for_caller(next_side, action) {
+                return Some(next_side);
+            }
+
+            if self.side_acts_for_caller(next_side.flip(), action) {
+                return Some(next_side.flip());
+            }
+
+            None
+        }
+
+        /// Returns side of the caller, preferring the side next in turn
+        /// when caller plays both sides
+        fn caller_side(&self) -> Option<Side> {
+            let next_side = self.game.side_next_in_turn();
+
+            if self.side_belongs_to_caller(next_side) {
+                return Some(next_side);
+            }
+
+            if self.side_belongs_to_caller(next_side.flip()) {
+                return Some(next_side.flip());
+            }
+
+            None
+        }
+
+        /// Returns time control of the `side` player, which includes extra time
+        /// given by the handicap
+        fn side_time_control(&self, side: Side) -> TimeControl {
+            Self::handicap_time_control(*self.time_control, self.info.handicap, side)
+        }
+
+        fn handicap_time_control(
+            time_control: TimeControl,
+            handicap: Option<Handicap>,
+            side: Side,
+        ) -> TimeControl {
+            let extra = handicap.map_or(0, |handicap| handicap.extra_time(side));
+
+            time_control.with_extra_time(extra)
+        }
+
+        fn side_blocks_left(&self, side: Side) -> u32 {
+            let blocks_left = match side {
+                Side::White => self.info.white_blocks_left,
+                Side::Black => self.info.black_blocks_left,
+            };
+
+            if self.side_has_next_turn(side)
+                && self.info.status == Status::Active
+                && !self.channel_open()
+            {
+                let block_diff = self.clock_diff_since_last_move();
+                let blocks_left_plus_1 = blocks_left
+                    .saturating_add(self.time_control.delay())
+                    .saturating_add(1);
+
+                if blocks_left_plus_1 < block_diff {
+                    return 0;
+                }
+
+                return blocks_left_plus_1 - block_diff;
+            }
+
+            blocks_left
+        }
+
+        fn clock_now(&self) -> u64 {
+            match self.info.clock_unit {
+                ClockUnit::Blocks => self.env().block_number() as u64,
+                ClockUnit::Milliseconds => self.env().block_timestamp(),
+            }
+        }
+
+        fn clock_diff_since_last_move(&self) -> u32 {
+            let last_move = match self.info.clock_unit {
+                ClockUnit::Blocks => self.info.last_move_block as u64,
+                ClockUnit::Milliseconds => self.info.last_move_timestamp,
+            };
+
+            // Paused clocks stand still at the time of the pause
+            let now = match (self.info.paused, self.info.clock_unit) {
+                (false, _) => self.clock_now(),
+                (true, ClockUnit::Blocks) => self.info.paused_block as u64,
+                (true, ClockUnit::Milliseconds) => self.info.paused_timestamp,
+            };
+
+            core::cmp::min(now - last_move, u32::MAX as u64) as u32
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use core::convert::TryInto;
+        use ink_env::AccountId;
+        use ink_lang as ink;
+
+        /// Events of the contract as they are recorded, in declaration order
+        #[derive(Decode)]
+        enum Event {
+            DrawOfferUpdate(DrawOfferUpdate),
+            BoardUpdate(BoardUpdate),
+            TakebackRequest(TakebackRequest),
+            TakebackAccept(TakebackAccept),
+            TakebackDecline(TakebackDecline),
+            DelegateUpdate(DelegateUpdate),
+            ChannelUpdate(ChannelUpdate),
+            ClockPause(ClockPause),
+            TimeAdded(TimeAdded),
+            Adjudicated(Adjudicated),
+            CollectibleMinted(CollectibleMinted),
+            Committed(Committed),
+            TimeoutReported(TimeoutReported),
+            CheckpointSubmitted(CheckpointSubmitted),
+            GameOver(GameOver),
+        }
+
+        #[ink::test]
+        fn make_move() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x01; 32]);
+
+            let mut chess = DotChess::new(white, black, 1, 1);
+
+            chess.make_move(mov("d2d3")).unwrap();
+            chess.make_move(mov("g7g6")).unwrap();
+        }
+
+        #[ink::test]
+        fn event_indices_match_declaration_order() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+
+            let mut chess = DotChess::new(white, black, 10, 1);
+
+            set_caller(white);
+            chess.make_move(mov("e2e4")).unwrap();
+            chess.offer_draw(true).unwrap();
+            chess.request_takeback(1).unwrap();
+
+            set_caller(black);
+            chess.accept_takeback().unwrap();
+
+            set_caller(white);
+            chess.resign().unwrap();
+
+            let indices: alloc::vec::Vec<u8> = ink_env::test::recorded_events()
+                .map(|event| event.data[0])
+                .collect();
+
+            assert_eq!(
+                indices,
+                [
+                    event_index::BOARD_UPDATE,
+                    event_index::DRAW_OFFER_UPDATE,
+                    event_index::TAKEBACK_REQUEST,
+                    event_index::TAKEBACK_ACCEPT,
+                    event_index::GAME_OVER,
+                ]
+            );
+        }
+
+        #[ink::test]
+        fn board_update_describes_move() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x01; 32]);
+
+            let mut chess = DotChess::new(white, black, 10, 10);
+
+            for m in &["f2f3", "e7e5", "g2g4", "d8h4"] {
+                chess.make_move(mov(m)).unwrap();
+            }
+
+            let updates: alloc::vec::Vec<BoardUpdate> = ink_env::test::recorded_events()
+                .filter_map(|event| match <Event as scale::Decode>::decode(&mut &event.data[..]) {
+                    Ok(Event::BoardUpdate(update)) => Some(update),
+                    _ => None,
+                })
+                .collect();
+
+            assert_eq!(updates.len(), 4);
+
+            let last = &updates[3];
+            assert_eq!(last.ply, 4);
+            assert_eq!(last.san, "Qh4#");
+            assert_eq!(last.piece, Piece::Queen);
+            assert_eq!(last.captured, None);
+            assert_eq!(
+                last.flags,
+                MoveFlags {
+                    capture: false,
+                    check: true,
+                    checkmate: true,
+                }
+            );
+            // Side to move can still move in the current block
+            assert_eq!(last.white_time_left, 11);
+            assert_eq!(last.black_time_left, 10);
+            assert_eq!(chess.moves().len(), 4);
+            assert_eq!(
+                chess.status(),
+                Status::Finished {
+                    winner: Some(Side::Black),
+                    reason: GameOverReason::Checkmate,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn mate_with_last_block_wins() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x01; 32]);
+            let time_control = TimeControl::Fischer {
+                base: 3,
+                increment: 0,
+            };
+
+            let mut chess = DotChess::with_time_control(
+                white,
+                black,
+                time_control,
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+            );
+
+            for m in &["f2f3", "e7e5", "g2g4"] {
+                chess.make_move(mov(m)).unwrap();
+            }
+
+            // Black mates with its clock running out on the move
+            advance_blocks(3);
+            chess.make_move(mov("d8h4")).unwrap();
+
+            assert_eq!(chess.blocks_left(Side::Black), 0);
+            assert_eq!(
+                chess.status(),
+                Status::Finished {
+                    winner: Some(Side::Black),
+                    reason: GameOverReason::Checkmate,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn fischer_increment() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x01; 32]);
+            let time_control = TimeControl::Fischer {
+                base: 10,
+                increment: 2,
+            };
+
+            let mut chess = DotChess::with_time_control(
+                white,
+                black,
+                time_control,
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+            );
+
+            advance_blocks(3);
+            chess.make_move(mov("d2d3")).unwrap();
+
+            assert_eq!(chess.blocks_left(Side::White), 9);
+        }
+
+        #[ink::test]
+        fn committed_move_is_charged() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x01; 32]);
+            let time_control = TimeControl::Fischer {
+                base: 10,
+                increment: 2,
+            };
+
+            let mut chess = DotChess::with_time_control(
+                white,
+                black,
+                time_control,
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+            );
+
+            let salt = [0x07; 32];
+
+            advance_blocks(3);
+            chess
+                .commit(chess.move_commitment_hash(mov("d2d3"), salt))
+                .unwrap();
+            advance_blocks(4);
+
+            assert_eq!(chess.blocks_left(Side::White), 4);
+
+            chess.reveal_move(mov("d2d3"), salt).unwrap();
+
+            assert_eq!(chess.blocks_left(Side::White), 5);
+            assert_eq!(chess.commitment(Side::White), None);
+        }
+
+        #[ink::test]
+        fn commitment_must_be_revealed_in_window() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x01; 32]);
+
+            let mut chess = DotChess::new(white, black, 100, 0);
+
+            let salt = [0x07; 32];
+            let hash = chess.move_commitment_hash(mov("d2d3"), salt);
+
+            assert_eq!(
+                chess.reveal_move(mov("d2d3"), salt),
+                Err(Error::NoCommitment)
+            );
+
+            chess.commit(hash).unwrap();
+
+            assert_eq!(
+                chess.reveal_move(mov("d2d3"), salt),
+                Err(Error::RevealTooEarly)
+            );
+
+            advance_blocks(1);
+
+            assert_eq!(
+                chess.reveal_move(mov("d2d4"), salt),
+                Err(Error::CommitmentMismatch)
+            );
+            assert_eq!(
+                chess.reveal_move(mov("d2d3"), [0x08; 32]),
+                Err(Error::CommitmentMismatch)
+            );
+
+            advance_blocks(REVEAL_WINDOW);
+
+            assert_eq!(chess.commitment(Side::White), None);
+            assert_eq!(
+                chess.reveal_move(mov("d2d3"), salt),
+                Err(Error::RevealWindowClosed)
+            );
+            assert_eq!(chess.blocks_left(Side::White), 100 - REVEAL_WINDOW);
+        }
+
+        #[ink::test]
+        fn committed_draw_offer() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x01; 32]);
+
+            let mut chess = DotChess::new(white, black, 100, 0);
+
+            let salt = [0x07; 32];
+
+            chess
+                .commit(chess.draw_offer_commitment_hash(true, salt))
+                .unwrap();
+            advance_blocks(1);
+
+            assert_eq!(
+                chess.reveal_draw_offer(false, salt),
+                Err(Error::CommitmentMismatch)
+            );
+
+            chess.reveal_draw_offer(true, salt).unwrap();
+
+            assert!(chess.side_draw_offer(Side::White));
+            assert_eq!(chess.commitment(Side::White), None);
+        }
+
+        #[ink::test]
+        fn side_not_to_move_commits_draw_offer() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+
+            let mut chess = DotChess::new(white, black, 100, 0);
+
+            let salt = [0x07; 32];
+            let move_hash = chess.move_commitment_hash(mov("e7e5"), salt);
+            let draw_hash = chess.draw_offer_commitment_hash(true, salt);
+
+            set_caller(black);
+            chess.commit(draw_hash).unwrap();
+            assert_eq!(chess.commitment(Side::Black).map(|c| c.hash), Some(draw_hash));
+            assert_eq!(chess.commitment(Side::White), None);
+
+            chess.commit(move_hash).unwrap();
+            advance_blocks(1);
+
+            assert_eq!(
+                chess.reveal_move(mov("e7e5"), salt),
+                Err(Error::InvalidCaller)
+            );
+
+            chess.commit(draw_hash).unwrap();
+            advance_blocks(1);
+            chess.reveal_draw_offer(true, salt).unwrap();
+
+            assert!(chess.side_draw_offer(Side::Black));
+            assert_eq!(chess.commitment(Side::Black), None);
+        }
+
+        #[ink::test]
+        fn either_side_offers_draw() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+
+            let mut chess = DotChess::new(white, black, 100, 0);
+
+            set_caller(black);
+            chess.offer_draw(true).unwrap();
+
+            assert!(chess.side_draw_offer(Side::Black));
+
+            set_caller(white);
+            chess.offer_draw(true).unwrap();
+
+            assert_eq!(
+                chess.status(),
+                Status::Finished {
+                    winner: None,
+                    reason: GameOverReason::DrawAgreement,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn draw_offer_lifecycle() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+
+            let mut chess = DotChess::new(white, black, 100, 0);
+
+            set_caller(white);
+            assert_eq!(chess.decline_draw(), Err(Error::NoDrawOffer));
+
+            chess.make_move(mov("d2d3")).unwrap();
+            chess.offer_draw(true).unwrap();
+
+            // Offer stands until the opponent moves
+            set_caller(black);
+            chess.make_move(mov("d7d6")).unwrap();
+
+            assert!(!chess.side_draw_offer(Side::White));
+
+            // Offering again is rate limited
+            set_caller(white);
+            assert_eq!(chess.offer_draw(true), Err(Error::DrawOfferTooSoon));
+
+            let moves = [("e2e3", "e7e6"), ("f2f3", "f7f6"), ("g2g3", "g7g6")];
+
+            for (white_move, black_move) in moves.iter() {
+                set_caller(white);
+                chess.make_move(mov(white_move)).unwrap();
+                set_caller(black);
+                chess.make_move(mov(black_move)).unwrap();
+            }
+
+            set_caller(white);
+            chess.offer_draw(true).unwrap();
+
+            set_caller(black);
+            chess.decline_draw().unwrap();
+
+            assert!(!chess.side_draw_offer(Side::White));
+            assert_eq!(chess.status(), Status::Active);
+
+            let changes: alloc::vec::Vec<(Side, DrawOfferChange)> =
+                ink_env::test::recorded_events()
+                    .filter_map(|event| {
+                        match <Event as scale::Decode>::decode(&mut &event.data[..]) {
+                            Ok(Event::DrawOfferUpdate(update)) => {
+                                Some((update.side, update.change))
+                            }
+                            _ => None,
+                        }
+                    })
+                    .collect();
+
+            assert_eq!(
+                changes,
+                vec![
+                    (Side::White, DrawOfferChange::Offered),
+                    (Side::White, DrawOfferChange::Expired),
+                    (Side::White, DrawOfferChange::Offered),
+                    (Side::White, DrawOfferChange::Declined),
+                ]
+            );
+        }
+
+        #[ink::test]
+        fn handicap_sets_up_position_and_clocks() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+            let time_control = TimeControl::Fischer {
+                base: 10,
+                increment: 0,
+            };
+
+            let chess = DotChess::with_handicap(
+                white,
+                black,
+                time_control,
+                ClockUnit::Blocks,
+                Handicap::QueenOdds { giver: Side::White },
+                Settings::default(),
+            );
+
+            assert_eq!(
+                chess.fen().unwrap(),
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNB1KBNR w KQkq - 0 1"
+            );
+            assert_eq!(
+                chess.handicap(),
+                Some(Handicap::QueenOdds { giver: Side::White })
+            );
+
+            let handicap = Handicap::ExtraTime {
+                receiver: Side::Black,
+                amount: 5,
+            };
+
+            let mut chess = DotChess::with_handicap(
+                white,
+                black,
+                time_control,
+                ClockUnit::Blocks,
+                handicap,
+                Settings::default(),
+            );
+
+            assert_eq!(chess.blocks_left(Side::Black), 15);
+
+            set_caller(white);
+            chess.resign().unwrap();
+
+            let game_over = ink_env::test::recorded_events()
+                .filter_map(|event| {
+                    match <Event as scale::Decode>::decode(&mut &event.data[..]) {
+                        Ok(Event::GameOver(game_over)) => Some(game_over),
+                        _ => None,
+                    }
+                })
+                .last()
+                .unwrap();
+
+            assert_eq!(game_over.handicap, Some(handicap));
+        }
+
+        #[ink::test]
+        fn extra_time_applies_to_every_correspondence_move() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+            let arbiter = AccountId::from([0x03; 32]);
+            let handicap = Handicap::ExtraTime {
+                receiver: Side::Black,
+                amount: 5,
+            };
+
+            let mut chess = DotChess::with_handicap(
+                white,
+                black,
+                TimeControl::Correspondence { per_move: 10 },
+                ClockUnit::Blocks,
+                handicap,
+                Settings {
+                    arbiter: Some(arbiter),
+                    ..Settings::default()
+                },
+            );
+
+            assert_eq!(chess.arbiter(), Some(arbiter));
+            assert_eq!(chess.blocks_left(Side::White), 11);
+            assert_eq!(chess.blocks_left(Side::Black), 15);
+
+            set_caller(white);
+            chess.make_move(mov("e2e4")).unwrap();
+
+            // Black would be out of time without the extra blocks
+            set_caller(black);
+            advance_blocks(12);
+            chess.make_move(mov("e7e5")).unwrap();
+
+            assert_eq!(chess.blocks_left(Side::White), 11);
+            assert_eq!(chess.blocks_left(Side::Black), 15);
+        }
+
+        #[ink::test]
+        fn timestamp_clock() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x01; 32]);
+            let time_control = TimeControl::Fischer {
+                base: 60_000,
+                increment: 1_000,
+            };
+
+            let mut chess = DotChess::with_time_control(
+                white,
+                black,
+                time_control,
+                ClockUnit::Milliseconds,
+                Game::FEN_NEW_GAME.into(),
+            );
+
+            let start = block_timestamp();
+            advance_blocks(3);
+            let elapsed = (block_timestamp() - start) as u32;
+
+            chess.make_move(mov("d2d3")).unwrap();
+
+            assert_eq!(
+                chess.blocks_left(Side::White),
+                60_000 - elapsed + 1_000
+            );
+        }
+
+        fn block_timestamp() -> u64 {
+            ink_env::block_timestamp::<ink_env::DefaultEnvironment>().unwrap()
+        }
+
+        #[ink::test]
+        fn threefold_repetition_is_claimable() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x01; 32]);
+
+            let mut chess = DotChess::new(white, black, 100, 1);
+
+            for m in ["g1f3", "g8f6", "f3g1", "f6g8"].iter() {
+                chess.make_move(mov(m)).unwrap();
+            }
+
+            assert_eq!(chess.claim_draw(None), Err(Error::DrawNotClaimable));
+
+            for m in ["g1f3", "g8f6", "f3g1", "f6g8"].iter() {
+                chess.make_move(mov(m)).unwrap();
+            }
+
+            assert_eq!(chess.repetition_count(chess.game.zobrist()), 3);
+            assert_eq!(
+                chess.fen().unwrap(),
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 8 5"
+            );
+
+            chess.claim_draw(None).unwrap();
+
+            assert_eq!(
+                chess.status(),
+                Status::Finished {
+                    winner: None,
+                    reason: GameOverReason::ThreefoldRepetition,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn fifty_move_rule_is_claimable_with_move() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+
+            let mut chess = DotChess::from_fen(
+                white,
+                black,
+                100,
+                1,
+                "6k1/5ppp/8/8/8/8/8/R5K1 w - - 99 80".into(),
+            );
+
+            assert_eq!(chess.claim_draw(None), Err(Error::DrawNotClaimable));
+
+            chess.claim_draw(Some(mov("a1a2"))).unwrap();
+
+            assert_eq!(
+                chess.status(),
+                Status::Finished {
+                    winner: None,
+                    reason: GameOverReason::FiftyMoveRule,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn mating_move_cannot_claim_draw() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+
+            let mut chess = DotChess::from_fen(
+                white,
+                black,
+                100,
+                1,
+                "6k1/5ppp/8/8/8/8/8/R5K1 w - - 99 80".into(),
+            );
+
+            assert_eq!(
+                chess.claim_draw(Some(mov("a1a8"))),
+                Err(Error::DrawNotClaimable)
+            );
+            assert_eq!(chess.status(), Status::Active);
+
+            chess.make_move(mov("a1a8")).unwrap();
+
+            assert_eq!(
+                chess.status(),
+                Status::Finished {
+                    winner: Some(Side::White),
+                    reason: GameOverReason::Checkmate,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn move_history() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x01; 32]);
+
+            let mut chess = DotChess::new(white, black, 10, 1);
+
+            chess.make_move(mov("e2e4")).unwrap();
+            chess.make_move(mov("e7e5")).unwrap();
+            chess.make_move(mov("g1f3")).unwrap();
+
+            assert_eq!(chess.move_count(), 3);
+            assert_eq!(chess.moves()[0].0, mov("e2e4").encode());
+            assert_eq!(chess.position_at(0).unwrap(), Game::FEN_NEW_GAME);
+            assert_eq!(chess.position_at(3).unwrap(), chess.fen().unwrap());
+            assert_eq!(chess.position_at(4), Err(Error::InvalidPly));
+            assert_eq!(
+                chess.pgn().unwrap(),
+                "[Event \"DotChess\"]\n[Result \"*\"]\n\n1. e4 e5 2. Nf3 *"
+            );
+        }
+
+        #[ink::test]
+        fn position_queries() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+
+            let chess = DotChess::new(white, black, 10, 1);
+
+            assert_eq!(chess.legal_moves().len(), 20);
+            assert_eq!(
+                chess.legal_moves_from(Square::G1),
+                [mov("g1f3"), mov("g1h3")]
+            );
+            assert!(chess.legal_moves_from(Square::G8).is_empty());
+            assert!(!chess.is_check());
+            assert_eq!(chess.side_to_move(), Side::White);
+            assert_eq!(chess.status(), Status::Active);
+            assert_eq!(chess.players(), (white, black));
+            assert_eq!(chess.clocks().white_time_left, 11);
+            assert_eq!(chess.clocks().black_time_left, 10);
+        }
+
+        #[ink::test]
+        fn string_messages() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+
+            let mut chess = DotChess::new(white, black, 10, 1);
+
+            assert_eq!(chess.make_move_str("d2d9".into()), Err(Error::InvalidRank));
+
+            chess.make_move_str("d2d3".into()).unwrap();
+
+            assert_eq!(chess.side_to_move_str(), "black");
+            assert_eq!(chess.blocks_left_str("white".into()), Ok(10));
+            assert_eq!(chess.blocks_left_str("red".into()), Err(Error::InvalidSide));
+            assert_eq!(chess.side_draw_offer_str("black".into()), Ok(false));
+            assert_eq!(chess.legal_moves_str().len(), 20);
+            assert_eq!(
+                chess.legal_moves_from_str("g8".into()),
+                Ok(vec![String::from("g8f6"), String::from("g8h6")])
+            );
+        }
+
+        #[ink::test]
+        fn resignation_finishes_game() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+
+            let mut chess = DotChess::new(white, black, 10, 1);
+
+            chess.resign().unwrap();
+
+            assert_eq!(
+                chess.status(),
+                Status::Finished {
+                    winner: Some(Side::Black),
+                    reason: GameOverReason::Resignation,
+                }
+            );
+            assert_eq!(chess.make_move(mov("d2d3")), Err(Error::GameAlreadyOver));
+        }
+
+        #[ink::test]
+        fn fee_is_collected_by_beneficiary_after_grace_period() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+
+            let mut chess = DotChess::new(white, black, 10, 1);
+
+            set_caller(white);
+            assert_eq!(chess.collect_fee(), Err(Error::GameNotOver));
+
+            chess.resign().unwrap();
+
+            assert_eq!(chess.collect_fee(), Err(Error::InvalidCaller));
+
+            set_caller(FEE_BENEFICIARY.into());
+            assert_eq!(chess.collect_fee(), Err(Error::GracePeriodNotOver));
+        }
+
+        #[ink::test]
+        fn staked_game_waits_for_deposits() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+            let time_control = TimeControl::Fischer {
+                base: 10,
+                increment: 1,
+            };
+
+            let mut chess = DotChess::with_stake(
+                white,
+                black,
+                time_control,
+                ClockUnit::Blocks,
+                100,
+                Game::FEN_NEW_GAME.into(),
+            );
+
+            assert_eq!(chess.status(), Status::Funding);
+            assert_eq!(chess.make_move(mov("d2d3")), Err(Error::GameNotFunded));
+            assert_eq!(chess.deposit(), Err(Error::InvalidDeposit));
+        }
+
+        #[ink::test]
+        fn takeback() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+
+            let mut chess = DotChess::new(white, black, 10, 1);
+
+            set_caller(white);
+            chess.make_move(mov("d2d3")).unwrap();
+            set_caller(black);
+            chess.make_move(mov("g7g6")).unwrap();
+
+            set_caller(white);
+            chess.request_takeback(2).unwrap();
+            assert_eq!(chess.accept_takeback(), Err(Error::InvalidCaller));
+
+            set_caller(black);
+            chess.accept_takeback().unwrap();
+
+            assert_eq!(chess.fen().unwrap(), Game::FEN_NEW_GAME);
+            assert_eq!(chess.zobrist.len(), 1);
+            assert_eq!(chess.history.len(), 0);
+        }
+
+        #[ink::test]
+        fn takeback_request_expires() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+
+            let mut chess = DotChess::new(white, black, 10, 1);
+
+            set_caller(white);
+            chess.make_move(mov("d2d3")).unwrap();
+            chess.request_takeback(1).unwrap();
+
+            // Opponent moves on instead of accepting
+            set_caller(black);
+            chess.make_move(mov("g7g6")).unwrap();
+
+            assert_eq!(chess.accept_takeback(), Err(Error::NoTakebackRequest));
+        }
+
+        #[ink::test]
+        fn takeback_request_expires_when_requester_moves() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+
+            let mut chess = DotChess::new(white, black, 10, 1);
+
+            set_caller(white);
+            chess.make_move(mov("d2d3")).unwrap();
+            set_caller(black);
+            chess.make_move(mov("g7g6")).unwrap();
+
+            set_caller(white);
+            chess.request_takeback(1).unwrap();
+            chess.make_move(mov("e2e4")).unwrap();
+
+            set_caller(black);
+            assert_eq!(chess.accept_takeback(), Err(Error::NoTakebackRequest));
+            assert_eq!(chess.move_count(), 3);
+        }
+
+        #[ink::test]
+        fn delegate_acts_within_permissions() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+            let session = AccountId::from([0x03; 32]);
+            let permissions = Permissions {
+                make_move: true,
+                offer_draw: true,
+                claim_draw: false,
+                takeback: false,
+            };
+
+            let mut chess = DotChess::new(white, black, 10, 1);
+
+            assert_eq!(
+                chess.authorize_delegate(session, 0, permissions),
+                Err(Error::InvalidExpiry)
+            );
+
+            chess.authorize_delegate(session, 5, permissions).unwrap();
+
+            set_caller(session);
+            assert_eq!(chess.make_move(mov("d2d3")), Ok(()));
+            assert_eq!(chess.revoke_delegate(), Err(Error::InvalidCaller));
+
+            set_caller(black);
+            chess.make_move(mov("g7g6")).unwrap();
+
+            set_caller(session);
+            assert_eq!(chess.resign(), Err(Error::InvalidCaller));
+            assert_eq!(chess.request_takeback(1), Err(Error::InvalidCaller));
+            assert_eq!(chess.offer_draw(true), Ok(()));
+
+            advance_blocks(5);
+
+            assert_eq!(chess.delegate_of(Side::White), None);
+            assert_eq!(chess.make_move(mov("e2e4")), Err(Error::InvalidCaller));
+        }
+
+        #[ink::test]
+        fn delegate_can_be_revoked() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+            let session = AccountId::from([0x03; 32]);
+            let permissions = Permissions {
+                make_move: true,
+                offer_draw: false,
+                claim_draw: false,
+                takeback: false,
+            };
+
+            let mut chess = DotChess::new(white, black, 10, 1);
+
+            chess.authorize_delegate(session, 100, permissions).unwrap();
+            assert_eq!(
+                chess.delegate_of(Side::White),
+                Some(Delegate {
+                    account: session,
+                    expires: 100,
+                    permissions,
+                })
+            );
+
+            chess.revoke_delegate().unwrap();
+            assert_eq!(chess.delegate_of(Side::White), None);
+
+            set_caller(session);
+            assert_eq!(chess.make_move(mov("d2d3")), Err(Error::InvalidCaller));
+        }
+
+        #[ink::test]
+        fn checkpoint_replays_signed_moves() {
+            let white = account(1);
+            let black = account(2);
+
+            let mut chess = DotChess::new(white, black, 10, 1);
+
+            set_caller(white);
+            chess.open_channel().unwrap();
+            assert!(!chess.channel_open());
+
+            set_caller(black);
+            chess.open_channel().unwrap();
+            assert!(chess.channel_open());
+
+            // Clocks do not run while playing off-chain
+            advance_blocks(20);
+            assert_eq!(chess.report_abandonment(), Err(Error::NotOutOfTime));
+
+            let e4 = sign_move(&chess, 1, &["e2e4"]);
+            let e5 = sign_move(&chess, 2, &["e2e4", "e7e5"]);
+            let forged = sign_move(&chess, 1, &["e2e4", "e7e5"]);
+
+            assert_eq!(
+                chess.checkpoint(vec![e4, forged]),
+                Err(Error::InvalidSignature)
+            );
+            assert_eq!(chess.checkpoint(vec![e5]), Err(Error::InvalidPly));
+            assert_eq!(chess.pending_checkpoint(), None);
+
+            set_caller(white);
+            chess.checkpoint(vec![e4]).unwrap();
+            assert_eq!(chess.pending_checkpoint(), Some((1, 20 + DISPUTE_PERIOD)));
+
+            // Game stays off-chain during the dispute period
+            assert_eq!(chess.make_move(mov("d2d4")), Err(Error::ChannelOpen));
+            assert_eq!(chess.offer_draw(true), Err(Error::ChannelOpen));
+            assert_eq!(chess.apply_checkpoint(), Err(Error::DisputePeriodNotOver));
+            assert_eq!(chess.move_count(), 0);
+
+            // Opponent submits the longer sequence
+            set_caller(black);
+            chess.checkpoint(vec![e4, e5]).unwrap();
+            assert_eq!(chess.checkpoint(vec![e4]), Err(Error::CheckpointNotLonger));
+            assert_eq!(chess.pending_checkpoint(), Some((2, 20 + DISPUTE_PERIOD)));
+
+            advance_blocks(DISPUTE_PERIOD + 1);
+            assert_eq!(chess.checkpoint(vec![e4, e5]), Err(Error::DisputePeriodOver));
+            chess.apply_checkpoint().unwrap();
+
+            assert!(!chess.channel_open());
+            assert_eq!(chess.move_count(), 2);
+            assert_eq!(chess.side_to_move(), Side::White);
+            assert_eq!(chess.pending_checkpoint(), None);
+            assert_eq!(chess.checkpoint(vec![]), Err(Error::ChannelNotOpen));
+            assert_eq!(chess.apply_checkpoint(), Err(Error::NoCheckpoint));
+
+            // Clock of the side to move starts running again
+            advance_blocks(20);
+            assert_eq!(chess.report_abandonment(), Ok(()));
+        }
+
+        #[ink::test]
+        fn checkpoint_equivocation_loses() {
+            let white = account(1);
+            let black = account(2);
+
+            let mut chess = DotChess::new(white, black, 10, 1);
+
+            set_caller(white);
+            chess.open_channel().unwrap();
+            set_caller(black);
+            chess.open_channel().unwrap();
+
+            let e4 = sign_move(&chess, 1, &["e2e4"]);
+            let e5 = sign_move(&chess, 2, &["e2e4", "e7e5"]);
+            let d4 = sign_move(&chess, 1, &["d2d4"]);
+
+            // White signed both e4 and d4 as the first ply
+            set_caller(white);
+            chess.checkpoint(vec![d4]).unwrap();
+
+            set_caller(black);
+            chess.checkpoint(vec![e4, e5]).unwrap();
+
+            assert!(!chess.channel_open());
+            assert_eq!(chess.move_count(), 0);
+            assert_eq!(
+                chess.status(),
+                Status::Finished {
+                    winner: Some(Side::Black),
+                    reason: GameOverReason::Equivocation,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn checkpoint_keeps_clocks() {
+            let white = account(1);
+            let black = account(2);
+
+            let mut chess = DotChess::with_time_control(
+                white,
+                black,
+                TimeControl::Fischer {
+                    base: 10,
+                    increment: 5,
+                },
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+            );
+
+            set_caller(white);
+            chess.open_channel().unwrap();
+            set_caller(black);
+            chess.open_channel().unwrap();
+
+            let e4 = sign_move(&chess, 1, &["e2e4"]);
+            let e5 = sign_move(&chess, 2, &["e2e4", "e7e5"]);
+
+            chess.checkpoint(vec![e4, e5]).unwrap();
+            advance_blocks(DISPUTE_PERIOD + 1);
+            chess.apply_checkpoint().unwrap();
+
+            // Plies played off-chain earn no increment
+            assert_eq!(chess.move_count(), 2);
+            assert_eq!(chess.blocks_left(Side::White), 11);
+            assert_eq!(chess.blocks_left(Side::Black), 10);
+        }
+
+        #[ink::test]
+        fn settlement_requires_both_signatures() {
+            let white = account(1);
+            let black = account(2);
+
+            let mut chess = DotChess::new(white, black, 10, 1);
+
+            let hash = chess.settlement_hash(Some(Side::Black));
+            let white_signature = sign(1, &hash);
+            let black_signature = sign(2, &hash);
+
+            assert_eq!(
+                chess.settle(Some(Side::Black), white_signature, white_signature),
+                Err(Error::InvalidSignature)
+            );
+            assert_eq!(
+                chess.settle(None, white_signature, black_signature),
+                Err(Error::InvalidSignature)
+            );
+
+            chess
+                .settle(Some(Side::Black), white_signature, black_signature)
+                .unwrap();
+
+            assert_eq!(
+                chess.status(),
+                Status::Finished {
+                    winner: Some(Side::Black),
+                    reason: GameOverReason::Settlement,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn arbiter_pauses_clocks_and_adjudicates() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+            let arbiter = AccountId::from([0x03; 32]);
+            let time_control = TimeControl::Fischer {
+                base: 10,
+                increment: 1,
+            };
+
+            let mut chess = DotChess::with_settings(
+                white,
+                black,
+                time_control,
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+                Settings {
+                    arbiter: Some(arbiter),
+                    ..Settings::default()
+                },
+            );
+
+            assert_eq!(chess.arbiter(), Some(arbiter));
+            assert_eq!(chess.adjudicate(None), Err(Error::InvalidCaller));
+
+            set_caller(arbiter);
+            chess.pause_clocks(true).unwrap();
+            advance_blocks(50);
+
+            set_caller(white);
+            assert_eq!(chess.make_move(mov("d2d3")), Err(Error::GamePaused));
+
+            set_caller(arbiter);
+            chess.pause_clocks(false).unwrap();
+            chess.add_time(Side::Black, 5).unwrap();
+
+            assert_eq!(chess.blocks_left(Side::White), 11);
+            assert_eq!(chess.blocks_left(Side::Black), 15);
+
+            chess.adjudicate(Some(Side::Black)).unwrap();
+
+            assert_eq!(
+                chess.status(),
+                Status::Finished {
+                    winner: Some(Side::Black),
+                    reason: GameOverReason::Adjudication,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn arbiter_adds_time_up_to_clock_limit() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+            let arbiter = AccountId::from([0x03; 32]);
+            let time_control = TimeControl::Fischer {
+                base: 10,
+                increment: 1,
+            };
+
+            let mut chess = DotChess::with_settings(
+                white,
+                black,
+                time_control,
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+                Settings {
+                    arbiter: Some(arbiter),
+                    ..Settings::default()
+                },
+            );
+
+            set_caller(arbiter);
+            chess.add_time(Side::Black, u32::MAX - 10).unwrap();
+            assert_eq!(chess.add_time(Side::Black, 1), Err(Error::TimeOverflow));
+            assert_eq!(chess.blocks_left(Side::Black), u32::MAX);
+
+            // Clock of the side to move and the increment saturate
+            chess.add_time(Side::White, u32::MAX - 10).unwrap();
+            assert_eq!(chess.blocks_left(Side::White), u32::MAX);
+
+            set_caller(white);
+            chess.make_move(mov("e2e4")).unwrap();
+            assert_eq!(chess.blocks_left(Side::White), u32::MAX);
+        }
+
+        #[ink::test]
+        fn pause_extends_dispute_period() {
+            let white = account(1);
+            let black = account(2);
+            let arbiter = account(3);
+
+            let mut chess = DotChess::with_settings(
+                white,
+                black,
+                TimeControl::Correspondence { per_move: 100 },
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+                Settings {
+                    arbiter: Some(arbiter),
+                    ..Settings::default()
+                },
+            );
+
+            set_caller(white);
+            chess.open_channel().unwrap();
+            set_caller(black);
+            chess.open_channel().unwrap();
+
+            let e4 = sign_move(&chess, 1, &["e2e4"]);
+
+            set_caller(white);
+            chess.checkpoint(vec![e4]).unwrap();
+
+            set_caller(arbiter);
+            chess.pause_clocks(true).unwrap();
+            advance_blocks(50);
+            chess.pause_clocks(false).unwrap();
+
+            assert_eq!(chess.pending_checkpoint(), Some((1, 50 + DISPUTE_PERIOD)));
+
+            advance_blocks(DISPUTE_PERIOD);
+            assert_eq!(chess.apply_checkpoint(), Err(Error::DisputePeriodNotOver));
+        }
+
+        #[ink::test]
+        fn arbiter_voids_unfunded_game() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+            let arbiter = AccountId::from([0x03; 32]);
+
+            let mut chess = DotChess::with_settings(
+                white,
+                black,
+                TimeControl::Correspondence { per_move: 100 },
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+                Settings {
+                    stake: 100,
+                    arbiter: Some(arbiter),
+                    ..Settings::default()
+                },
+            );
+
+            set_caller(arbiter);
+            chess.void().unwrap();
+
+            assert_eq!(
+                chess.status(),
+                Status::Finished {
+                    winner: None,
+                    reason: GameOverReason::Void,
+                }
+            );
+            assert_eq!(chess.void(), Err(Error::GameAlreadyOver));
+        }
+
+        #[ink::test]
+        fn void_splits_endowment() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().unwrap();
+            let arbiter = AccountId::from([0x03; 32]);
+
+            let mut chess = DotChess::with_settings(
+                accounts.alice,
+                accounts.bob,
+                TimeControl::Correspondence { per_move: 100 },
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+                Settings {
+                    arbiter: Some(arbiter),
+                    ..Settings::default()
+                },
+            );
+
+            let contract = ink_env::account_id::<ink_env::DefaultEnvironment>().unwrap();
+            let minimum_balance =
+                ink_env::minimum_balance::<ink_env::DefaultEnvironment>().unwrap();
+            let balance = |account| {
+                ink_env::test::get_account_balance::<ink_env::DefaultEnvironment>(account).unwrap()
+            };
+
+            ink_env::test::set_account_balance::<ink_env::DefaultEnvironment>(
+                contract,
+                minimum_balance + 200,
+            )
+            .unwrap();
+
+            let alice_balance = balance(accounts.alice);
+            let bob_balance = balance(accounts.bob);
+
+            set_caller(arbiter);
+            chess.void().unwrap();
+
+            assert_eq!(balance(accounts.alice), alice_balance + 100);
+            assert_eq!(balance(accounts.bob), bob_balance + 100);
+        }
+
+        #[ink::test]
+        fn token_stakes() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+            let token = AccountId::from([0x09; 32]);
+
+            let mut chess = DotChess::with_settings(
+                white,
+                black,
+                TimeControl::Correspondence { per_move: 100 },
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+                Settings {
+                    stake: 100,
+                    token: Some(token),
+                    ..Settings::default()
+                },
+            );
+
+            let contract = ink_env::account_id::<ink_env::DefaultEnvironment>().unwrap();
+
+            token::mock::mint(token, white, 100);
+            token::mock::mint(token, black, 100);
+            token::mock::approve(token, white, contract, 100);
+
+            set_caller(white);
+            chess.deposit().unwrap();
+
+            set_caller(black);
+            assert_eq!(chess.deposit(), Err(Error::TransferFailed));
+            assert_eq!(chess.status(), Status::Funding);
+
+            token::mock::approve(token, black, contract, 100);
+            chess.deposit().unwrap();
+
+            assert_eq!(chess.status(), Status::Active);
+            assert_eq!(token::mock::balance_of(token, contract), 200);
+
+            set_caller(white);
+            chess.resign().unwrap();
+
+            assert_eq!(token::mock::balance_of(token, white), 0);
+            assert_eq!(token::mock::balance_of(token, black), 198);
+            assert_eq!(
+                token::mock::balance_of(token, FEE_BENEFICIARY.into()),
+                2
+            );
+        }
+
+        #[ink::test]
+        fn winner_gets_collectible() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+            let collection = AccountId::from([0x0c; 32]);
+
+            let mut chess = DotChess::with_settings(
+                white,
+                black,
+                TimeControl::Correspondence { per_move: 100 },
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+                Settings {
+                    collection: Some(collection),
+                    ..Settings::default()
+                },
+            );
+
+            chess.make_move(mov("d2d3")).unwrap();
+            assert!(collection::mock::take_tokens(collection).is_empty());
+
+            set_caller(black);
+            chess.resign().unwrap();
+
+            let contract = ink_env::account_id::<ink_env::DefaultEnvironment>().unwrap();
+            let id = collection::Id::Bytes((contract, Side::White).encode());
+            let tokens = collection::mock::take_tokens(collection);
+
+            assert_eq!(tokens.len(), 1);
+            assert_eq!(tokens[0], (id.clone(), white));
+
+            let record =
+                collection::mock::attribute(collection, &id, collection::RECORD_ATTRIBUTE).unwrap();
+
+            assert_eq!(
+                Collectible::decode(&mut record.as_slice()).unwrap(),
+                Collectible {
+                    game: contract,
+                    side: Side::White,
+                    moves: vec![mov("d2d3").encode()],
+                    start_fen: Game::FEN_NEW_GAME.into(),
+                    winner: Some(Side::White),
+                    reason: GameOverReason::Resignation,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn voided_game_is_not_minted() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+            let arbiter = AccountId::from([0x03; 32]);
+            let collection = AccountId::from([0x0c; 32]);
+
+            let mut chess = DotChess::with_settings(
+                white,
+                black,
+                TimeControl::Correspondence { per_move: 100 },
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+                Settings {
+                    arbiter: Some(arbiter),
+                    collection: Some(collection),
+                    ..Settings::default()
+                },
+            );
+
+            set_caller(arbiter);
+            chess.void().unwrap();
+
+            assert!(collection::mock::take_tokens(collection).is_empty());
+        }
+
+        #[ink::test]
+        fn third_party_reporter_gets_bounty() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+            let reporter = AccountId::from([0x03; 32]);
+            let token = AccountId::from([0x09; 32]);
+
+            let mut chess = DotChess::with_settings(
+                white,
+                black,
+                TimeControl::Correspondence { per_move: 10 },
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+                Settings {
+                    stake: 100,
+                    token: Some(token),
+                    reporter_bounty: 5,
+                    ..Settings::default()
+                },
+            );
+
+            let contract = ink_env::account_id::<ink_env::DefaultEnvironment>().unwrap();
+
+            for player in [white, black].iter() {
+                token::mock::mint(token, *player, 100);
+                token::mock::approve(token, *player, contract, 100);
+                set_caller(*player);
+                chess.deposit().unwrap();
+            }
+
+            set_caller(reporter);
+            assert_eq!(chess.report_timeout(Side::White), Err(Error::NotOutOfTime));
+
+            advance_blocks(12);
+
+            assert_eq!(chess.report_timeout(Side::Black), Err(Error::NotOutOfTime));
+
+            chess.report_timeout(Side::White).unwrap();
+
+            assert_eq!(
+                chess.status(),
+                Status::Finished {
+                    winner: Some(Side::Black),
+                    reason: GameOverReason::Abandonment,
+                }
+            );
+
+            // Bounty is capped at the fee of 2, the pot is paid in full
+            assert_eq!(token::mock::balance_of(token, reporter), 2);
+            assert_eq!(token::mock::balance_of(token, black), 198);
+            assert_eq!(token::mock::balance_of(token, FEE_BENEFICIARY.into()), 0);
+        }
+
+        #[ink::test]
+        fn player_reporting_timeout_gets_no_bounty() {
+            let white = AccountId::from([0x01; 32]);
+            let black = AccountId::from([0x02; 32]);
+            let token = AccountId::from([0x09; 32]);
+
+            let mut chess = DotChess::with_settings(
+                white,
+                black,
+                TimeControl::Correspondence { per_move: 10 },
+                ClockUnit::Blocks,
+                Game::FEN_NEW_GAME.into(),
+                Settings {
+                    stake: 100,
+                    token: Some(token),
+                    reporter_bounty: 5,
+                    ..Settings::default()
+                },
+            );
+
+            let contract = ink_env::account_id::<ink_env::DefaultEnvironment>().unwrap();
+
+            for player in [white, black].iter() {
+                token::mock::mint(token, *player, 100);
+                token::mock::approve(token, *player, contract, 100);
+                set_caller(*player);
+                chess.deposit().unwrap();
+            }
+
+            advance_blocks(12);
+            chess.report_abandonment().unwrap();
+
+            assert_eq!(token::mock::balance_of(token, black), 198);
+        }
+
+        fn secret_key(seed: u8) -> secp256k1::SecretKey {
+            secp256k1::SecretKey::from_slice(&[seed; 32]).unwrap()
+        }
+
+        fn account(seed: u8) -> AccountId {
+            let public_key =
+                secp256k1::PublicKey::from_secret_key(secp256k1::SECP256K1, &secret_key(seed));
+            let mut account = [0; 32];
+
+            ink_env::hash_bytes::<Blake2x256>(&public_key.serialize(), &mut account);
+
+            account.into()
+        }
+
+        fn sign(seed: u8, hash: &[u8; 32]) -> Signature {
+            let message = secp256k1::Message::from_slice(hash).unwrap();
+            let (recovery_id, compact) = secp256k1::SECP256K1
+                .sign_ecdsa_recoverable(&message, &secret_key(seed))
+                .serialize_compact();
+
+            let mut signature = [0; 65];
+            signature[..64].copy_from_slice(&compact);
+            signature[64] = recovery_id.to_i32() as u8;
+
+            Signature(signature)
+        }
+
+        /// Signs the last of `moves` played from the starting position
+        fn sign_move(chess: &DotChess, seed: u8, moves: &[&str]) -> SignedMove {
+            let mut game = Game::new(Game::FEN_NEW_GAME).unwrap();
+
+            for m in moves {
+                game = game.make_move(&mov(m)).unwrap();
+            }
+
+            let ply = moves.len() as u32 - 1;
+            let last_move = mov(moves[moves.len() - 1]);
+            let hash = chess.signed_move_hash(ply, last_move, game.zobrist());
+
+            SignedMove {
+                ply,
+                mov: last_move,
+                zobrist: game.zobrist(),
+                signature: sign(seed, &hash),
+            }
+        }
+
+        fn mov(mov: &str) -> Mov {
+            mov.try_into().unwrap()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
+                caller,
+                ink_env::account_id::<ink_env::DefaultEnvironment>().unwrap(),
+                1000000,
+                0,
+                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])),
+            );
+        }
+
+        fn advance_blocks(n: u32) {
+            for _ in 0..n {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>().unwrap();
+            }
         }
     }
 }
@@ -0,0 +1,77 @@
+//! ECDSA signatures of moves and results agreed off-chain
+
+use crate::common::Result;
+use scale::{Decode, Encode};
+
+/// Recoverable ECDSA signature, the compact signature followed by the recovery id
+#[derive(Copy, Clone, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq))]
+pub struct Signature(pub [u8; 65]);
+
+#[cfg(feature = "std")]
+impl scale_info::TypeInfo for Signature {
+    type Identity = Self;
+
+    fn type_info() -> scale_info::Type {
+        scale_info::TypeDefArray::new(65, scale_info::MetaType::new::<u8>()).into()
+    }
+}
+
+impl Signature {
+    /// Returns the compressed public key of the key pair which signed `hash`
+    pub fn recover(&self, hash: &[u8; 32]) -> Result<[u8; 33]> {
+        ecdsa::recover(&self.0, hash)
+    }
+}
+
+#[cfg(not(test))]
+mod ecdsa {
+    use crate::common::{Error, Result};
+    use ink_env::chain_extension::{ChainExtensionMethod, FromStatusCode};
+
+    /// Function id of the chain extension recovering ECDSA public keys, the runtime
+    /// is expected to answer it with the compressed public key of the signer
+    const ECDSA_RECOVER: u32 = 0x0000_ec01;
+
+    struct RecoveryFailed;
+
+    impl FromStatusCode for RecoveryFailed {
+        fn from_status_code(status_code: u32) -> core::result::Result<(), Self> {
+            match status_code {
+                0 => Ok(()),
+                _ => Err(RecoveryFailed),
+            }
+        }
+    }
+
+    pub fn recover(signature: &[u8; 65], hash: &[u8; 32]) -> Result<[u8; 33]> {
+        ChainExtensionMethod::build(ECDSA_RECOVER)
+            .input::<([u8; 65], [u8; 32])>()
+            .output::<[u8; 33]>()
+            .handle_error_code::<RecoveryFailed>()
+            .call(&(*signature, *hash))
+            .map_err(|_| Error::InvalidSignature)
+    }
+}
+
+/// Recovers public keys locally, chain extensions of the off-chain environment
+/// cannot be registered from outside of `ink_env`
+#[cfg(test)]
+mod ecdsa {
+    use crate::common::{Error, Result};
+    use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+    use secp256k1::{Message, SECP256K1};
+
+    pub fn recover(signature: &[u8; 65], hash: &[u8; 32]) -> Result<[u8; 33]> {
+        let recovery_id =
+            RecoveryId::from_i32(signature[64] as i32).map_err(|_| Error::InvalidSignature)?;
+        let signature = RecoverableSignature::from_compact(&signature[..64], recovery_id)
+            .map_err(|_| Error::InvalidSignature)?;
+        let message = Message::from_slice(hash).map_err(|_| Error::InvalidSignature)?;
+
+        SECP256K1
+            .recover_ecdsa(&message, &signature)
+            .map(|public_key| public_key.serialize())
+            .map_err(|_| Error::InvalidSignature)
+    }
+}
@@ -0,0 +1,19 @@
+use crate::board::Side;
+use crate::gameover::GameOverReason;
+use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
+use scale::{Decode, Encode};
+
+/// Game lifecycle status
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, SpreadLayout, PackedLayout)]
+#[cfg_attr(feature = "std", derive(Debug, scale_info::TypeInfo, StorageLayout))]
+pub enum Status {
+    /// Players have yet to deposit their stakes
+    Funding,
+    /// Game is in progress
+    Active,
+    /// Game ended due to `reason`, unless it is drawn there is also a `winner`
+    Finished {
+        winner: Option<Side>,
+        reason: GameOverReason,
+    },
+}
@@ -0,0 +1,254 @@
+use crate::board::Side;
+use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
+use scale::{Decode, Encode};
+
+/// Unit the game clock runs in
+#[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+#[cfg_attr(
+    feature = "std",
+    derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+)]
+pub enum ClockUnit {
+    /// Time is measured by block number differences
+    Blocks,
+    /// Time is measured by block timestamp differences in milliseconds
+    Milliseconds,
+}
+
+/// Time control of a game, all amounts are expressed in blocks, or milliseconds
+/// if the game clock runs in `ClockUnit::Milliseconds`
+#[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+#[cfg_attr(
+    feature = "std",
+    derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+)]
+pub enum TimeControl {
+    /// `base` blocks for the whole game, `increment` blocks are added after every move
+    Fischer { base: u32, increment: u32 },
+    /// `base` blocks for the whole game, after every move the player gets back
+    /// the blocks spent on it, but at most `delay` blocks
+    Bronstein { base: u32, delay: u32 },
+    /// `base` blocks for the whole game, the clock starts running only after
+    /// `delay` blocks have passed since the last move
+    SimpleDelay { base: u32, delay: u32 },
+    /// `base` blocks for the first `moves` moves, after the `moves`th move `bonus` blocks
+    /// are added and every following move adds `increment` blocks
+    MultiStage {
+        moves: u32,
+        base: u32,
+        bonus: u32,
+        increment: u32,
+    },
+    /// Every move has to be made within `per_move` blocks, unused blocks do not carry over
+    Correspondence { per_move: u32 },
+    /// `base` blocks for the whole game, `increment` blocks are added after every move
+    /// once the fullmove number after the move exceeds 40, that is from white's 41st
+    /// and black's 40th move on. Games created by `DotChess::new` use this time control.
+    Classic { base: u32, increment: u32 },
+}
+
+impl TimeControl {
+    /// Blocks each side starts the game with
+    pub fn base(&self) -> u32 {
+        use TimeControl::*;
+
+        match *self {
+            Fischer { base, .. } => base,
+            Bronstein { base, .. } => base,
+            SimpleDelay { base, .. } => base,
+            MultiStage { base, .. } => base,
+            Correspondence { per_move } => per_move,
+            Classic { base, .. } => base,
+        }
+    }
+
+    /// Returns this time control with `extra` blocks added to the base, or to every move
+    /// of a correspondence game
+    pub fn with_extra_time(&self, extra: u32) -> Self {
+        use TimeControl::*;
+
+        match *self {
+            Fischer { base, increment } => Fischer {
+                base: base + extra,
+                increment,
+            },
+            Bronstein { base, delay } => Bronstein {
+                base: base + extra,
+                delay,
+            },
+            SimpleDelay { base, delay } => SimpleDelay {
+                base: base + extra,
+                delay,
+            },
+            MultiStage {
+                moves,
+                base,
+                bonus,
+                increment,
+            } => MultiStage {
+                moves,
+                base: base + extra,
+                bonus,
+                increment,
+            },
+            Correspondence { per_move } => Correspondence {
+                per_move: per_move + extra,
+            },
+            Classic { base, increment } => Classic {
+                base: base + extra,
+                increment,
+            },
+        }
+    }
+
+    /// Blocks that may pass after the last move before the clock starts running
+    pub fn delay(&self) -> u32 {
+        match *self {
+            TimeControl::SimpleDelay { delay, .. } => delay,
+            _ => 0,
+        }
+    }
+
+    /// Returns blocks left for a `side` player who had `blocks_left` blocks, spent
+    /// `blocks_spent` blocks on their `move_number`th move and made it in time
+    pub fn blocks_left_after_move(
+        &self,
+        blocks_left: u32,
+        blocks_spent: u32,
+        move_number: u32,
+        side: Side,
+    ) -> u32 {
+        use TimeControl::*;
+
+        let blocks_left = blocks_left.saturating_sub(blocks_spent.saturating_sub(self.delay()));
+
+        match *self {
+            Fischer { increment, .. } => blocks_left.saturating_add(increment),
+            Bronstein { delay, .. } => {
+                blocks_left.saturating_add(core::cmp::min(blocks_spent, delay))
+            }
+            SimpleDelay { .. } => blocks_left,
+            MultiStage {
+                moves,
+                bonus,
+                increment,
+                ..
+            } => {
+                if move_number == moves {
+                    blocks_left.saturating_add(bonus)
+                } else if move_number > moves {
+                    blocks_left.saturating_add(increment)
+                } else {
+                    blocks_left
+                }
+            }
+            Correspondence { per_move } => per_move,
+            Classic { increment, .. } => {
+                // Fullmove number increases after black's move
+                let next_move_number = match side {
+                    Side::White => move_number,
+                    Side::Black => move_number + 1,
+                };
+
+                if next_move_number > 40 {
+                    blocks_left.saturating_add(increment)
+                } else {
+                    blocks_left
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fischer_adds_increment_from_first_move() {
+        let tc = TimeControl::Fischer {
+            base: 100,
+            increment: 5,
+        };
+
+        assert_eq!(tc.blocks_left_after_move(100, 10, 1, Side::White), 95);
+    }
+
+    #[test]
+    fn bronstein_returns_at_most_delay() {
+        let tc = TimeControl::Bronstein {
+            base: 100,
+            delay: 5,
+        };
+
+        assert_eq!(tc.blocks_left_after_move(100, 3, 1, Side::White), 100);
+        assert_eq!(tc.blocks_left_after_move(100, 10, 1, Side::White), 95);
+    }
+
+    #[test]
+    fn simple_delay_charges_blocks_past_delay() {
+        let tc = TimeControl::SimpleDelay {
+            base: 100,
+            delay: 5,
+        };
+
+        assert_eq!(tc.blocks_left_after_move(100, 3, 1, Side::White), 100);
+        assert_eq!(tc.blocks_left_after_move(100, 10, 1, Side::White), 95);
+    }
+
+    #[test]
+    fn multi_stage_adds_bonus_after_stage() {
+        let tc = TimeControl::MultiStage {
+            moves: 40,
+            base: 100,
+            bonus: 50,
+            increment: 2,
+        };
+
+        assert_eq!(tc.blocks_left_after_move(100, 10, 39, Side::White), 90);
+        assert_eq!(tc.blocks_left_after_move(100, 10, 40, Side::White), 140);
+        assert_eq!(tc.blocks_left_after_move(100, 10, 41, Side::White), 92);
+    }
+
+    #[test]
+    fn correspondence_resets_per_move() {
+        let tc = TimeControl::Correspondence { per_move: 100 };
+
+        assert_eq!(tc.blocks_left_after_move(100, 90, 1, Side::White), 100);
+    }
+
+    #[test]
+    fn extra_time_is_added_to_base_or_every_move() {
+        let fischer = TimeControl::Fischer {
+            base: 100,
+            increment: 5,
+        };
+        let correspondence = TimeControl::Correspondence { per_move: 100 };
+
+        assert_eq!(
+            fischer.with_extra_time(20),
+            TimeControl::Fischer {
+                base: 120,
+                increment: 5,
+            }
+        );
+        assert_eq!(
+            correspondence
+                .with_extra_time(20)
+                .blocks_left_after_move(120, 90, 1, Side::White),
+            120
+        );
+    }
+
+    #[test]
+    fn classic_adds_increment_after_40th_move() {
+        let tc = TimeControl::Classic {
+            base: 100,
+            increment: 5,
+        };
+
+        assert_eq!(tc.blocks_left_after_move(100, 10, 40, Side::White), 90);
+        assert_eq!(tc.blocks_left_after_move(100, 10, 40, Side::Black), 95);
+        assert_eq!(tc.blocks_left_after_move(100, 10, 41, Side::White), 95);
+    }
+}
@@ -0,0 +1,208 @@
+//! Calls to PSP22 token contracts holding game stakes
+
+use crate::common::Result;
+use ink_env::AccountId;
+use scale::{Decode, Encode};
+
+type Balance = <ink_env::DefaultEnvironment as ink_env::Environment>::Balance;
+
+/// Error returned by PSP22 token contracts
+#[derive(Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, scale_info::TypeInfo))]
+pub enum Psp22Error {
+    Custom(alloc::string::String),
+    InsufficientBalance,
+    InsufficientAllowance,
+    ZeroRecipientAddress,
+    ZeroSenderAddress,
+    SafeTransferCheckFailed(alloc::string::String),
+}
+
+#[cfg(not(test))]
+mod psp22 {
+    use super::{Balance, Psp22Error};
+    use crate::common::{Error, Result};
+    use ink_env::call::{build_call, utils::ReturnType, ExecutionInput, Selector};
+    use ink_env::{AccountId, DefaultEnvironment};
+
+    /// Selector of `PSP22::transfer`
+    const TRANSFER_SELECTOR: [u8; 4] = [0xdb, 0x20, 0xf9, 0xf5];
+    /// Selector of `PSP22::transfer_from`
+    const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x54, 0xb3, 0xc7, 0x6e];
+
+    pub fn transfer(token: AccountId, to: AccountId, value: Balance) -> Result<()> {
+        build_call::<DefaultEnvironment>()
+            .callee(token)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(TRANSFER_SELECTOR))
+                    .push_arg(to)
+                    .push_arg(value)
+                    .push_arg(alloc::vec::Vec::<u8>::new()),
+            )
+            .returns::<ReturnType<core::result::Result<(), Psp22Error>>>()
+            .fire()
+            .map_err(|_| Error::TransferFailed)?
+            .map_err(|_| Error::TransferFailed)
+    }
+
+    pub fn transfer_from(
+        token: AccountId,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+    ) -> Result<()> {
+        build_call::<DefaultEnvironment>()
+            .callee(token)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(TRANSFER_FROM_SELECTOR))
+                    .push_arg(from)
+                    .push_arg(to)
+                    .push_arg(value)
+                    .push_arg(alloc::vec::Vec::<u8>::new()),
+            )
+            .returns::<ReturnType<core::result::Result<(), Psp22Error>>>()
+            .fire()
+            .map_err(|_| Error::TransferFailed)?
+            .map_err(|_| Error::TransferFailed)
+    }
+}
+
+/// Mock PSP22 contracts, the off-chain environment cannot dispatch cross-contract calls
+#[cfg(test)]
+pub mod mock {
+    use super::{Balance, Psp22Error};
+    use ink_env::AccountId;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct Psp22 {
+        balances: HashMap<AccountId, Balance>,
+        allowances: HashMap<(AccountId, AccountId), Balance>,
+    }
+
+    thread_local! {
+        static TOKENS: RefCell<HashMap<AccountId, Psp22>> = RefCell::new(HashMap::new());
+    }
+
+    fn with_token<T>(token: AccountId, f: impl FnOnce(&mut Psp22) -> T) -> T {
+        TOKENS.with(|tokens| f(tokens.borrow_mut().entry(token).or_default()))
+    }
+
+    /// Creates `value` of `token` for `owner`
+    pub fn mint(token: AccountId, owner: AccountId, value: Balance) {
+        with_token(token, |psp22| {
+            *psp22.balances.entry(owner).or_default() += value
+        });
+    }
+
+    /// Allows `spender` to transfer `value` of `owner`s `token`
+    pub fn approve(token: AccountId, owner: AccountId, spender: AccountId, value: Balance) {
+        with_token(token, |psp22| {
+            psp22.allowances.insert((owner, spender), value);
+        });
+    }
+
+    pub fn balance_of(token: AccountId, owner: AccountId) -> Balance {
+        with_token(token, |psp22| {
+            psp22.balances.get(&owner).copied().unwrap_or_default()
+        })
+    }
+
+    pub fn transfer(
+        token: AccountId,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+    ) -> Result<(), Psp22Error> {
+        with_token(token, |psp22| {
+            let balance = psp22.balances.get(&from).copied().unwrap_or_default();
+
+            if balance < value {
+                return Err(Psp22Error::InsufficientBalance);
+            }
+
+            psp22.balances.insert(from, balance - value);
+            *psp22.balances.entry(to).or_default() += value;
+
+            Ok(())
+        })
+    }
+
+    pub fn transfer_from(
+        token: AccountId,
+        spender: AccountId,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+    ) -> Result<(), Psp22Error> {
+        let allowance = with_token(token, |psp22| {
+            psp22
+                .allowances
+                .get(&(from, spender))
+                .copied()
+                .unwrap_or_default()
+        });
+
+        if allowance < value {
+            return Err(Psp22Error::InsufficientAllowance);
+        }
+
+        transfer(token, from, to, value)?;
+        approve(token, from, spender, allowance - value);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod psp22 {
+    use super::{mock, Balance};
+    use crate::common::{Error, Result};
+    use ink_env::{AccountId, DefaultEnvironment};
+
+    fn contract() -> AccountId {
+        ink_env::account_id::<DefaultEnvironment>().unwrap()
+    }
+
+    pub fn transfer(token: AccountId, to: AccountId, value: Balance) -> Result<()> {
+        mock::transfer(token, contract(), to, value).map_err(|_| Error::TransferFailed)
+    }
+
+    pub fn transfer_from(
+        token: AccountId,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+    ) -> Result<()> {
+        mock::transfer_from(token, contract(), from, to, value).map_err(|_| Error::TransferFailed)
+    }
+}
+
+/// Transfers `value` of `token` from the calling contract to `to`
+pub fn transfer(token: AccountId, to: AccountId, value: Balance) -> Result<()> {
+    if value == 0 {
+        return Ok(());
+    }
+
+    psp22::transfer(token, to, value)
+}
+
+/// Transfers `value` of `token` from `from` to `to`, the calling contract
+/// must be approved to spend it
+pub fn transfer_from(
+    token: AccountId,
+    from: AccountId,
+    to: AccountId,
+    value: Balance,
+) -> Result<()> {
+    if value == 0 {
+        return Ok(());
+    }
+
+    psp22::transfer_from(token, from, to, value)
+}
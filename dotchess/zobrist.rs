@@ -123,6 +123,18 @@ impl core::ops::BitXorAssign for ZobristHash {
     }
 }
 
+impl core::convert::From<u32> for ZobristHash {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl core::convert::Into<u32> for ZobristHash {
+    fn into(self) -> u32 {
+        self.0
+    }
+}
+
 impl ZobristHash {
     const SIDE_TURN_HASH_KEY_INDEX: usize = 780;
 
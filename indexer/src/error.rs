@@ -0,0 +1,52 @@
+use std::fmt;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Reading events or writing summaries failed
+    Io(std::io::Error),
+    /// Line of a JSON-lines file is not a valid event record
+    Json {
+        line: usize,
+        error: serde_json::Error,
+    },
+    /// SCALE file is not a sequence of event records
+    Scale(scale::Error),
+    /// Field of a JSON event record has invalid value
+    InvalidField { line: usize, field: &'static str },
+    /// Chess engine error
+    Chess(dotchess::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "{}", error),
+            Error::Json { line, error } => write!(f, "line {}: {}", line, error),
+            Error::Scale(error) => write!(f, "invalid SCALE record: {}", error),
+            Error::InvalidField { line, field } => write!(f, "line {}: invalid {}", line, field),
+            Error::Chess(error) => write!(f, "{:?}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl From<scale::Error> for Error {
+    fn from(error: scale::Error) -> Self {
+        Error::Scale(error)
+    }
+}
+
+impl From<dotchess::Error> for Error {
+    fn from(error: dotchess::Error) -> Self {
+        Error::Chess(error)
+    }
+}
@@ -0,0 +1,465 @@
+//! Contract events as read from JSON-lines or SCALE files
+//!
+//! A SCALE file is a plain concatenation of encoded `Record`s. A JSON-lines
+//! file holds one record per line, the event name is in the `event` field
+//! next to the event fields and the game account id:
+//!
+//! ```json
+//! {"game": "0x0101..01", "event": "DrawOfferUpdate", "side": "w", "offer": true, "change": "offered"}
+//! ```
+//!
+//! Sides are `w` or `b`, pieces are lowercase FEN letters, moves are in the
+//! format accepted by `Mov::try_from`, clock units are `blocks` or `milliseconds`,
+//! draw offer changes are `offered`, `withdrawn`, `declined` or `expired`, game
+//! over reasons are as returned by `GameOverReason::as_str` and the optional game
+//! over handicap is one of `pawn and move`, `knight odds <giver side>`, `queen odds
+//! <giver side>` or `extra time <receiver side> <amount>`.
+
+use crate::error::{Error, Result};
+use dotchess::{
+    ClockUnit, DrawOfferChange, GameOverReason, Handicap, Mov, MoveFlags, Piece, Side, ZobristHash,
+};
+use scale::{Decode, Encode};
+use serde::Deserialize;
+use std::convert::{TryFrom, TryInto};
+use std::io::BufRead;
+
+pub type GameId = [u8; 32];
+
+/// Event `event` emitted by the `game` contract
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct Record {
+    pub game: GameId,
+    pub event: Event,
+}
+
+/// Contract events the indexer consumes
+///
+/// Variants are SCALE encoded with the indices of `dotchess::event_index`
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub enum Event {
+    #[codec(index = 0)]
+    DrawOfferUpdate {
+        side: Side,
+        offer: bool,
+        change: DrawOfferChange,
+    },
+    #[codec(index = 1)]
+    BoardUpdate(BoardUpdate),
+    #[codec(index = 3)]
+    TakebackAccept {
+        side: Side,
+        n_plies: u32,
+        fen: String,
+    },
+    #[codec(index = 14)]
+    GameOver {
+        winner: Option<Side>,
+        reason: GameOverReason,
+        handicap: Option<Handicap>,
+    },
+}
+
+/// Fields of the `BoardUpdate` contract event, in the order they are encoded
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct BoardUpdate {
+    pub next_side: Side,
+    pub clock_unit: ClockUnit,
+    pub next_move_deadline: u64,
+    pub last_move: Mov,
+    pub last_side_time_left: u32,
+    pub fen: String,
+    pub ply: u32,
+    pub san: String,
+    pub flags: MoveFlags,
+    pub piece: Piece,
+    pub captured: Option<Piece>,
+    pub zobrist: ZobristHash,
+    pub white_time_left: u32,
+    pub black_time_left: u32,
+}
+
+/// Returns game id formatted as hex string
+pub fn game_id_hex(game: &GameId) -> String {
+    let hex: String = game.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    format!("0x{}", hex)
+}
+
+/// Reads records of a SCALE file
+pub fn read_scale(mut input: &[u8]) -> Result<Vec<Record>> {
+    let mut records = Vec::new();
+
+    while !input.is_empty() {
+        records.push(Record::decode(&mut input)?);
+    }
+
+    Ok(records)
+}
+
+/// Reads records of a JSON-lines file, blank lines are skipped
+pub fn read_json_lines(input: impl BufRead) -> Result<Vec<Record>> {
+    let mut records = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        let line_number = index + 1;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record: JsonRecord = serde_json::from_str(&line).map_err(|error| Error::Json {
+            line: line_number,
+            error,
+        })?;
+
+        records.push(record.into_record(line_number)?);
+    }
+
+    Ok(records)
+}
+
+#[derive(Deserialize)]
+struct JsonRecord {
+    game: String,
+    #[serde(flatten)]
+    event: JsonEvent,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "event")]
+enum JsonEvent {
+    DrawOfferUpdate {
+        side: String,
+        offer: bool,
+        change: String,
+    },
+    BoardUpdate {
+        next_side: String,
+        clock_unit: String,
+        next_move_deadline: u64,
+        last_move: String,
+        last_side_time_left: u32,
+        fen: String,
+        ply: u32,
+        san: String,
+        flags: JsonFlags,
+        piece: String,
+        captured: Option<String>,
+        zobrist: u32,
+        white_time_left: u32,
+        black_time_left: u32,
+    },
+    TakebackAccept {
+        side: String,
+        n_plies: u32,
+        fen: String,
+    },
+    GameOver {
+        winner: Option<String>,
+        reason: String,
+        #[serde(default)]
+        handicap: Option<String>,
+    },
+}
+
+#[derive(Deserialize)]
+struct JsonFlags {
+    capture: bool,
+    check: bool,
+    checkmate: bool,
+}
+
+impl JsonRecord {
+    fn into_record(self, line: usize) -> Result<Record> {
+        let invalid = |field| Error::InvalidField { line, field };
+
+        let game = parse_game_id(&self.game).ok_or_else(|| invalid("game"))?;
+
+        let event = match self.event {
+            JsonEvent::DrawOfferUpdate {
+                side,
+                offer,
+                change,
+            } => Event::DrawOfferUpdate {
+                side: parse_side(&side).ok_or_else(|| invalid("side"))?,
+                offer,
+                change: parse_draw_offer_change(&change).ok_or_else(|| invalid("change"))?,
+            },
+            JsonEvent::BoardUpdate {
+                next_side,
+                clock_unit,
+                next_move_deadline,
+                last_move,
+                last_side_time_left,
+                fen,
+                ply,
+                san,
+                flags,
+                piece,
+                captured,
+                zobrist,
+                white_time_left,
+                black_time_left,
+            } => Event::BoardUpdate(BoardUpdate {
+                next_side: parse_side(&next_side).ok_or_else(|| invalid("next_side"))?,
+                clock_unit: parse_clock_unit(&clock_unit).ok_or_else(|| invalid("clock_unit"))?,
+                next_move_deadline,
+                last_move: last_move
+                    .as_str()
+                    .try_into()
+                    .map_err(|_| invalid("last_move"))?,
+                last_side_time_left,
+                fen,
+                ply,
+                san,
+                flags: MoveFlags {
+                    capture: flags.capture,
+                    check: flags.check,
+                    checkmate: flags.checkmate,
+                },
+                piece: parse_piece(&piece).ok_or_else(|| invalid("piece"))?,
+                captured: match captured {
+                    Some(piece) => Some(parse_piece(&piece).ok_or_else(|| invalid("captured"))?),
+                    None => None,
+                },
+                zobrist: zobrist.into(),
+                white_time_left,
+                black_time_left,
+            }),
+            JsonEvent::TakebackAccept { side, n_plies, fen } => Event::TakebackAccept {
+                side: parse_side(&side).ok_or_else(|| invalid("side"))?,
+                n_plies,
+                fen,
+            },
+            JsonEvent::GameOver {
+                winner,
+                reason,
+                handicap,
+            } => Event::GameOver {
+                winner: match winner {
+                    Some(side) => Some(parse_side(&side).ok_or_else(|| invalid("winner"))?),
+                    None => None,
+                },
+                reason: parse_reason(&reason).ok_or_else(|| invalid("reason"))?,
+                handicap: match handicap {
+                    Some(handicap) => {
+                        Some(parse_handicap(&handicap).ok_or_else(|| invalid("handicap"))?)
+                    }
+                    None => None,
+                },
+            },
+        };
+
+        Ok(Record { game, event })
+    }
+}
+
+fn parse_game_id(value: &str) -> Option<GameId> {
+    let hex = value.strip_prefix("0x").unwrap_or(value);
+
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+
+    let mut game = [0; 32];
+
+    for (index, byte) in game.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * index..2 * index + 2], 16).ok()?;
+    }
+
+    Some(game)
+}
+
+fn parse_char<T: TryFrom<char>>(value: &str) -> Option<T> {
+    let mut chars = value.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(char), None) => char.try_into().ok(),
+        _ => None,
+    }
+}
+
+fn parse_side(value: &str) -> Option<Side> {
+    parse_char(value)
+}
+
+fn parse_piece(value: &str) -> Option<Piece> {
+    parse_char(value)
+}
+
+fn parse_clock_unit(value: &str) -> Option<ClockUnit> {
+    match value {
+        "blocks" => Some(ClockUnit::Blocks),
+        "milliseconds" => Some(ClockUnit::Milliseconds),
+        _ => None,
+    }
+}
+
+fn parse_draw_offer_change(value: &str) -> Option<DrawOfferChange> {
+    match value {
+        "offered" => Some(DrawOfferChange::Offered),
+        "withdrawn" => Some(DrawOfferChange::Withdrawn),
+        "declined" => Some(DrawOfferChange::Declined),
+        "expired" => Some(DrawOfferChange::Expired),
+        _ => None,
+    }
+}
+
+fn parse_handicap(value: &str) -> Option<Handicap> {
+    let words: Vec<&str> = value.split(' ').collect();
+
+    match words.as_slice() {
+        ["pawn", "and", "move"] => Some(Handicap::PawnAndMove),
+        ["knight", "odds", giver] => Some(Handicap::KnightOdds {
+            giver: parse_side(giver)?,
+        }),
+        ["queen", "odds", giver] => Some(Handicap::QueenOdds {
+            giver: parse_side(giver)?,
+        }),
+        ["extra", "time", receiver, amount] => Some(Handicap::ExtraTime {
+            receiver: parse_side(receiver)?,
+            amount: amount.parse().ok()?,
+        }),
+        _ => None,
+    }
+}
+
+fn parse_reason(value: &str) -> Option<GameOverReason> {
+    (0..=u8::MAX)
+        .filter_map(|reason| GameOverReason::try_from(reason).ok())
+        .find(|reason| reason.as_str() == value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dotchess::{event_index, Game};
+
+    const GAME: &str = "0x0101010101010101010101010101010101010101010101010101010101010101";
+
+    #[test]
+    fn json_lines() {
+        let input = format!(
+            "{}\n\n{}\n",
+            format!(
+                r#"{{"game": "{}", "event": "DrawOfferUpdate", "side": "w", "offer": true, "change": "offered"}}"#,
+                GAME
+            ),
+            format!(
+                r#"{{"game": "{}", "event": "GameOver", "winner": null, "reason": "draw agreement"}}"#,
+                GAME
+            ),
+        );
+
+        let records = read_json_lines(input.as_bytes()).unwrap();
+
+        assert_eq!(
+            records,
+            [
+                Record {
+                    game: [0x01; 32],
+                    event: Event::DrawOfferUpdate {
+                        side: Side::White,
+                        offer: true,
+                        change: DrawOfferChange::Offered,
+                    },
+                },
+                Record {
+                    game: [0x01; 32],
+                    event: Event::GameOver {
+                        winner: None,
+                        reason: GameOverReason::DrawAgreement,
+                        handicap: None,
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn json_lines_invalid_field() {
+        let input = format!(
+            r#"{{"game": "{}", "event": "DrawOfferUpdate", "side": "x", "offer": true, "change": "offered"}}"#,
+            GAME
+        );
+
+        match read_json_lines(input.as_bytes()) {
+            Err(Error::InvalidField { line: 1, field }) => assert_eq!(field, "side"),
+            _ => panic!("expected invalid side"),
+        }
+    }
+
+    #[test]
+    fn handicap_forms() {
+        assert_eq!(parse_handicap("pawn and move"), Some(Handicap::PawnAndMove));
+        assert_eq!(
+            parse_handicap("knight odds b"),
+            Some(Handicap::KnightOdds { giver: Side::Black })
+        );
+        assert_eq!(
+            parse_handicap("extra time w 30"),
+            Some(Handicap::ExtraTime {
+                receiver: Side::White,
+                amount: 30,
+            })
+        );
+        assert_eq!(parse_handicap("queen odds"), None);
+    }
+
+    #[test]
+    fn scale_records() {
+        let records = vec![
+            Record {
+                game: [0x01; 32],
+                event: Event::DrawOfferUpdate {
+                    side: Side::Black,
+                    offer: false,
+                    change: DrawOfferChange::Declined,
+                },
+            },
+            Record {
+                game: [0x02; 32],
+                event: Event::GameOver {
+                    winner: Some(Side::White),
+                    reason: GameOverReason::Resignation,
+                    handicap: Some(Handicap::ExtraTime {
+                        receiver: Side::Black,
+                        amount: 30,
+                    }),
+                },
+            },
+        ];
+
+        let bytes: Vec<u8> = records.iter().flat_map(|record| record.encode()).collect();
+
+        assert_eq!(read_scale(&bytes).unwrap(), records);
+        assert_eq!(game_id_hex(&[0x01; 32]), GAME);
+    }
+
+    #[test]
+    fn scale_indices_match_contract() {
+        let draw_offer = Event::DrawOfferUpdate {
+            side: Side::White,
+            offer: true,
+            change: DrawOfferChange::Offered,
+        };
+        let board_update = crate::replay::tests::board_updates(&["e2e4"]).remove(0);
+        let takeback = Event::TakebackAccept {
+            side: Side::White,
+            n_plies: 1,
+            fen: Game::FEN_NEW_GAME.into(),
+        };
+        let game_over = Event::GameOver {
+            winner: None,
+            reason: GameOverReason::Stalemate,
+            handicap: None,
+        };
+
+        assert_eq!(draw_offer.encode()[0], event_index::DRAW_OFFER_UPDATE);
+        assert_eq!(board_update.encode()[0], event_index::BOARD_UPDATE);
+        assert_eq!(takeback.encode()[0], event_index::TAKEBACK_ACCEPT);
+        assert_eq!(game_over.encode()[0], event_index::GAME_OVER);
+    }
+}
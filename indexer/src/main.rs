@@ -0,0 +1,68 @@
+//! Rebuilds games from contract events
+//!
+//! Usage: indexer EVENTS OUT_DIR [START_FEN]
+//!
+//! EVENTS is a SCALE file if its extension is `scale`, JSON-lines file otherwise.
+//! For each game a PGN and JSON summary named after the game account id is
+//! written to OUT_DIR. Games are assumed to start from START_FEN, which defaults
+//! to the initial position.
+
+extern crate dotchess;
+
+mod error;
+mod event;
+mod replay;
+mod summary;
+
+use crate::error::Result;
+use dotchess::Game;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::Path;
+use std::{env, process};
+
+pub fn main() {
+    if let Err(error) = run() {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<()> {
+    let args: Vec<String> = env::args().collect();
+
+    let events = Path::new(args.get(1).expect("events argument missing"));
+    let out_dir = Path::new(args.get(2).expect("output directory argument missing"));
+    let start_fen = args
+        .get(3)
+        .map(String::as_str)
+        .unwrap_or(Game::FEN_NEW_GAME);
+
+    let records = match events.extension().and_then(|extension| extension.to_str()) {
+        Some("scale") => event::read_scale(&fs::read(events)?)?,
+        _ => event::read_json_lines(BufReader::new(File::open(events)?))?,
+    };
+
+    fs::create_dir_all(out_dir)?;
+
+    for log in replay::replay(&records, start_fen)? {
+        let summary = summary::summary(&log)?;
+        let pgn = summary::pgn(&log).expect("formatting PGN failed");
+
+        fs::write(out_dir.join(format!("{}.pgn", summary.game)), pgn)?;
+        fs::write(
+            out_dir.join(format!("{}.json", summary.game)),
+            serde_json::to_string_pretty(&summary).expect("serializing summary failed"),
+        )?;
+
+        println!(
+            "{} {} {} plies, {} inconsistencies",
+            summary.game,
+            summary.result,
+            summary.plies,
+            summary.inconsistencies.len()
+        );
+    }
+
+    Ok(())
+}
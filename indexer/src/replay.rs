@@ -0,0 +1,503 @@
+//! Replaying games from their events
+
+use crate::event::{BoardUpdate, Event, GameId, Record};
+use dotchess::{ClockUnit, DrawOfferChange, Game, GameOverReason, Side};
+use std::collections::HashMap;
+
+/// Move of a replayed game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ply {
+    pub fullmove_number: u32,
+    pub side: Side,
+    pub san: String,
+}
+
+/// Event that does not agree with the replayed game, `ply` is the number of
+/// plies replayed before the event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inconsistency {
+    pub ply: u32,
+    pub message: String,
+}
+
+/// Game rebuilt from its events
+pub struct GameLog {
+    pub game_id: GameId,
+    pub start_fen: String,
+    pub plies: Vec<Ply>,
+    /// Number of draw offers made by white and black
+    pub draw_offers: [u32; 2],
+    /// Number of draw offers of white and black declined by the opponent
+    pub draw_offers_declined: [u32; 2],
+    pub result: Option<(Option<Side>, GameOverReason)>,
+    /// Clock unit, white and black time left after the last move
+    pub clocks: Option<(ClockUnit, u32, u32)>,
+    pub inconsistencies: Vec<Inconsistency>,
+    game: Game,
+    /// FEN before each ply, none if the ply was not replayed but resynced
+    positions: Vec<Option<String>>,
+}
+
+impl GameLog {
+    pub fn new(game_id: GameId, start_fen: &str) -> dotchess::Result<Self> {
+        Ok(Self {
+            game_id,
+            start_fen: start_fen.into(),
+            plies: Vec::new(),
+            draw_offers: [0, 0],
+            draw_offers_declined: [0, 0],
+            result: None,
+            clocks: None,
+            inconsistencies: Vec::new(),
+            game: Game::new(start_fen)?,
+            positions: Vec::new(),
+        })
+    }
+
+    /// Returns FEN of the replayed position
+    pub fn fen(&self) -> dotchess::Result<String> {
+        self.game.fen()
+    }
+
+    pub fn apply(&mut self, event: &Event) {
+        if self.result.is_some() {
+            self.inconsistent("event after game over".into());
+        }
+
+        match event {
+            Event::DrawOfferUpdate {
+                side,
+                offer,
+                change,
+            } => self.apply_draw_offer_update(*side, *offer, *change),
+            Event::BoardUpdate(update) => self.apply_board_update(update),
+            Event::TakebackAccept { n_plies, fen, .. } => self.apply_takeback(*n_plies, fen),
+            Event::GameOver { winner, reason, .. } => self.apply_game_over(*winner, *reason),
+        }
+    }
+
+    fn apply_board_update(&mut self, update: &BoardUpdate) {
+        let mov = update.last_move;
+        let mov_str: String = mov.into();
+        let side = self.game.side_next_in_turn();
+        let expected_ply = self.plies.len() as u32 + 1;
+
+        self.clocks = Some((
+            update.clock_unit,
+            update.white_time_left,
+            update.black_time_left,
+        ));
+
+        if update.ply != expected_ply {
+            self.inconsistent(format!(
+                "ply {} reported, expected {}",
+                update.ply, expected_ply
+            ));
+
+            return self.resync(update);
+        }
+
+        if update.next_side == side {
+            self.inconsistent(format!("{} reported to move twice", side_name(side)));
+        }
+
+        let san = match self.game.san(&mov) {
+            Ok(san) => san,
+            Err(error) => {
+                self.inconsistent(format!("move {} is invalid: {:?}", mov_str, error));
+
+                return self.resync(update);
+            }
+        };
+
+        let piece = self.game.piece_at(mov.from()).map(|(_, piece)| piece);
+        let captured = self.game.captured_piece(&mov);
+
+        let game_new = match self.game.make_move(&mov) {
+            Ok(game) => game,
+            Err(error) => {
+                self.inconsistent(format!("move {} is illegal: {:?}", mov_str, error));
+
+                return self.resync(update);
+            }
+        };
+
+        if san != update.san {
+            self.inconsistent(format!("SAN {} reported, expected {}", update.san, san));
+        }
+
+        if piece != Some(update.piece) {
+            self.inconsistent(format!("move {} reported with wrong piece", mov_str));
+        }
+
+        if captured != update.captured {
+            self.inconsistent(format!("move {} reported with wrong capture", mov_str));
+        }
+
+        let is_check = game_new.is_check();
+        let is_checkmate = is_check && !game_new.has_legal_moves();
+
+        if update.flags.capture != captured.is_some()
+            || update.flags.check != is_check
+            || update.flags.checkmate != is_checkmate
+        {
+            self.inconsistent(format!("move {} reported with wrong flags", mov_str));
+        }
+
+        match game_new.fen() {
+            Ok(fen) if fen == update.fen => {}
+            Ok(fen) => self.inconsistent(format!("FEN {} reported, expected {}", update.fen, fen)),
+            Err(error) => self.inconsistent(format!("FEN of replay failed: {:?}", error)),
+        }
+
+        if game_new.zobrist() != update.zobrist {
+            self.inconsistent(format!("move {} reported with wrong Zobrist hash", mov_str));
+        }
+
+        self.positions.push(self.game.fen().ok());
+        self.plies.push(Ply {
+            fullmove_number: self.game.fullmove_number(),
+            side,
+            san,
+        });
+
+        self.game = game_new;
+    }
+
+    /// Continues replay from the reported position after an inconsistent move
+    fn resync(&mut self, update: &BoardUpdate) {
+        match Game::new(&update.fen) {
+            Ok(game) => {
+                let side = game.side_next_in_turn();
+                let fullmove_number = match side {
+                    Side::White => game.fullmove_number().saturating_sub(1),
+                    Side::Black => game.fullmove_number(),
+                };
+
+                self.positions.push(None);
+                self.plies.push(Ply {
+                    fullmove_number,
+                    side: side.flip(),
+                    san: update.san.clone(),
+                });
+
+                self.game = game;
+            }
+            Err(error) => self.inconsistent(format!("FEN {} is invalid: {:?}", update.fen, error)),
+        }
+    }
+
+    /// Removes the last `n_plies` plies, replay continues from the reported position
+    fn apply_takeback(&mut self, n_plies: u32, fen: &str) {
+        let n_plies = n_plies as usize;
+
+        if n_plies > self.plies.len() {
+            self.inconsistent(format!(
+                "takeback of {} plies reported, only {} replayed",
+                n_plies,
+                self.plies.len()
+            ));
+        }
+
+        let remaining = self.plies.len().saturating_sub(n_plies);
+
+        if let Some(Some(expected)) = self.positions.get(remaining) {
+            if expected != fen {
+                self.inconsistent(format!(
+                    "FEN {} reported after takeback, expected {}",
+                    fen, expected
+                ));
+            }
+        }
+
+        self.plies.truncate(remaining);
+        self.positions.truncate(remaining);
+
+        match Game::new(fen) {
+            Ok(game) => self.game = game,
+            Err(error) => self.inconsistent(format!("FEN {} is invalid: {:?}", fen, error)),
+        }
+    }
+
+    fn apply_draw_offer_update(&mut self, side: Side, offer: bool, change: DrawOfferChange) {
+        match change {
+            DrawOfferChange::Offered => self.draw_offers[side as usize] += 1,
+            DrawOfferChange::Declined => self.draw_offers_declined[side as usize] += 1,
+            DrawOfferChange::Withdrawn | DrawOfferChange::Expired => {}
+        }
+
+        if offer != (change == DrawOfferChange::Offered) {
+            self.inconsistent(format!(
+                "draw offer of {} reported as {:?} with offer {}",
+                side_name(side),
+                change,
+                offer
+            ));
+        }
+    }
+
+    fn apply_game_over(&mut self, winner: Option<Side>, reason: GameOverReason) {
+        if self.result.is_some() {
+            return;
+        }
+
+        self.result = Some((winner, reason));
+
+        let has_legal_moves = self.game.has_legal_moves();
+        let last_side = self.game.side_next_in_turn().flip();
+
+        match reason {
+            GameOverReason::Checkmate => {
+                if has_legal_moves || !self.game.is_check() || winner != Some(last_side) {
+                    self.inconsistent("checkmate reported, position is not mate".into());
+                }
+            }
+            GameOverReason::Stalemate => {
+                if has_legal_moves || self.game.is_check() || winner.is_some() {
+                    self.inconsistent("stalemate reported, position is not stalemate".into());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn inconsistent(&mut self, message: String) {
+        self.inconsistencies.push(Inconsistency {
+            ply: self.plies.len() as u32,
+            message,
+        });
+    }
+}
+
+pub fn side_name(side: Side) -> &'static str {
+    match side {
+        Side::White => "white",
+        Side::Black => "black",
+    }
+}
+
+/// Replays `records` of all games, which are assumed to start from `start_fen`.
+/// Games are returned in order of their first event
+pub fn replay(records: &[Record], start_fen: &str) -> dotchess::Result<Vec<GameLog>> {
+    let mut games: Vec<GameLog> = Vec::new();
+    let mut index: HashMap<GameId, usize> = HashMap::new();
+
+    for record in records {
+        let position = match index.get(&record.game) {
+            Some(position) => *position,
+            None => {
+                games.push(GameLog::new(record.game, start_fen)?);
+                index.insert(record.game, games.len() - 1);
+                games.len() - 1
+            }
+        };
+
+        games[position].apply(&record.event);
+    }
+
+    Ok(games)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use dotchess::{Mov, MoveFlags};
+    use std::convert::TryInto;
+
+    /// Returns events the contract emits for `moves` played from the initial position
+    pub fn board_updates(moves: &[&str]) -> Vec<Event> {
+        let mut game = Game::new(Game::FEN_NEW_GAME).unwrap();
+        let mut events = Vec::new();
+
+        for (index, mov) in moves.iter().enumerate() {
+            let mov: Mov = (*mov).try_into().unwrap();
+            let san = game.san(&mov).unwrap();
+            let (_, piece) = game.piece_at(mov.from()).unwrap();
+            let captured = game.captured_piece(&mov);
+            let game_new = game.make_move(&mov).unwrap();
+
+            events.push(Event::BoardUpdate(BoardUpdate {
+                next_side: game_new.side_next_in_turn(),
+                clock_unit: ClockUnit::Blocks,
+                next_move_deadline: 100,
+                last_move: mov,
+                last_side_time_left: 10,
+                fen: game_new.fen().unwrap(),
+                ply: index as u32 + 1,
+                san,
+                flags: MoveFlags {
+                    capture: captured.is_some(),
+                    check: game_new.is_check(),
+                    checkmate: game_new.is_check() && !game_new.has_legal_moves(),
+                },
+                piece,
+                captured,
+                zobrist: game_new.zobrist(),
+                white_time_left: 10,
+                black_time_left: 10,
+            }));
+
+            game = game_new;
+        }
+
+        events
+    }
+
+    fn fen_after(moves: &[&str]) -> String {
+        match board_updates(moves).pop() {
+            Some(Event::BoardUpdate(update)) => update.fen,
+            _ => panic!("no moves"),
+        }
+    }
+
+    fn log(events: &[Event]) -> GameLog {
+        let mut log = GameLog::new([0x01; 32], Game::FEN_NEW_GAME).unwrap();
+
+        for event in events {
+            log.apply(event);
+        }
+
+        log
+    }
+
+    #[test]
+    fn consistent_game() {
+        let mut events = board_updates(&["f2f3", "e7e5", "g2g4", "d8h4"]);
+        events.push(Event::GameOver {
+            winner: Some(Side::Black),
+            reason: GameOverReason::Checkmate,
+            handicap: None,
+        });
+
+        let log = log(&events);
+
+        assert_eq!(log.inconsistencies, []);
+        assert_eq!(log.plies.len(), 4);
+        assert_eq!(log.plies[3].san, "Qh4#");
+        assert_eq!(
+            log.result,
+            Some((Some(Side::Black), GameOverReason::Checkmate))
+        );
+    }
+
+    #[test]
+    fn detects_wrong_fen_and_resyncs_after_gap() {
+        let mut events = board_updates(&["e2e4", "e7e5", "g1f3", "b8c6"]);
+
+        if let Event::BoardUpdate(update) = &mut events[1] {
+            update.fen = Game::FEN_NEW_GAME.into();
+        }
+
+        events.remove(2);
+
+        let log = log(&events);
+
+        assert_eq!(
+            log.inconsistencies
+                .iter()
+                .map(|inconsistency| inconsistency.ply)
+                .collect::<Vec<u32>>(),
+            [1, 2]
+        );
+        assert!(log.inconsistencies[1].message.starts_with("ply 4 reported"));
+
+        // Replay continues from the reported position
+        assert_eq!(log.plies.len(), 3);
+        assert_eq!(
+            log.fen().unwrap(),
+            "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3"
+        );
+    }
+
+    #[test]
+    fn detects_false_checkmate() {
+        let mut events = board_updates(&["e2e4"]);
+        events.push(Event::GameOver {
+            winner: Some(Side::White),
+            reason: GameOverReason::Checkmate,
+            handicap: None,
+        });
+        events.push(Event::DrawOfferUpdate {
+            side: Side::Black,
+            offer: true,
+            change: DrawOfferChange::Offered,
+        });
+
+        let log = log(&events);
+
+        assert_eq!(log.inconsistencies.len(), 2);
+        assert_eq!(log.inconsistencies[1].message, "event after game over");
+        assert_eq!(log.draw_offers, [0, 1]);
+    }
+
+    #[test]
+    fn takeback_removes_plies() {
+        let mut events = board_updates(&["e2e4", "e7e5", "g1f3"]);
+        events.push(Event::TakebackAccept {
+            side: Side::Black,
+            n_plies: 2,
+            fen: fen_after(&["e2e4"]),
+        });
+        events.extend(board_updates(&["e2e4", "c7c5"]).into_iter().skip(1));
+
+        let log = log(&events);
+
+        assert_eq!(log.inconsistencies, []);
+        assert_eq!(
+            log.plies
+                .iter()
+                .map(|ply| ply.san.as_str())
+                .collect::<Vec<&str>>(),
+            ["e4", "c5"]
+        );
+    }
+
+    #[test]
+    fn detects_wrong_takeback_position() {
+        let mut events = board_updates(&["e2e4", "e7e5"]);
+        events.push(Event::TakebackAccept {
+            side: Side::White,
+            n_plies: 2,
+            fen: fen_after(&["e2e4"]),
+        });
+
+        let log = log(&events);
+
+        assert_eq!(log.inconsistencies.len(), 1);
+        assert!(log.inconsistencies[0]
+            .message
+            .ends_with(&format!("expected {}", Game::FEN_NEW_GAME)));
+        assert_eq!(log.plies.len(), 0);
+        assert_eq!(log.fen().unwrap(), fen_after(&["e2e4"]));
+    }
+
+    #[test]
+    fn counts_draw_offer_changes() {
+        let changes = [
+            (Side::White, true, DrawOfferChange::Offered),
+            (Side::White, false, DrawOfferChange::Declined),
+            (Side::Black, true, DrawOfferChange::Offered),
+            (Side::Black, false, DrawOfferChange::Expired),
+            (Side::White, true, DrawOfferChange::Offered),
+            (Side::White, true, DrawOfferChange::Withdrawn),
+        ];
+        let events: Vec<Event> = changes
+            .iter()
+            .map(|(side, offer, change)| Event::DrawOfferUpdate {
+                side: *side,
+                offer: *offer,
+                change: *change,
+            })
+            .collect();
+
+        let log = log(&events);
+
+        assert_eq!(log.draw_offers, [2, 1]);
+        assert_eq!(log.draw_offers_declined, [1, 0]);
+        assert_eq!(log.inconsistencies.len(), 1);
+        assert_eq!(
+            log.inconsistencies[0].message,
+            "draw offer of white reported as Withdrawn with offer true"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! PGN and JSON summaries of replayed games
+
+use crate::event::game_id_hex;
+use crate::replay::{side_name, GameLog};
+use dotchess::{ClockUnit, Game, GameOverReason, Side};
+use serde::Serialize;
+use std::fmt::Write;
+
+#[derive(Serialize)]
+pub struct Summary {
+    pub game: String,
+    pub result: &'static str,
+    pub winner: Option<&'static str>,
+    pub reason: Option<&'static str>,
+    pub plies: usize,
+    pub start_fen: String,
+    pub final_fen: String,
+    pub draw_offers: DrawOffers,
+    pub clocks: Option<Clocks>,
+    pub inconsistencies: Vec<InconsistencySummary>,
+}
+
+#[derive(Serialize)]
+pub struct DrawOffers {
+    pub white: u32,
+    pub black: u32,
+    pub white_declined: u32,
+    pub black_declined: u32,
+}
+
+#[derive(Serialize)]
+pub struct Clocks {
+    pub unit: &'static str,
+    pub white_time_left: u32,
+    pub black_time_left: u32,
+}
+
+#[derive(Serialize)]
+pub struct InconsistencySummary {
+    pub ply: u32,
+    pub message: String,
+}
+
+/// Returns PGN result of the game, aborted, voided and unfinished games have unknown result
+pub fn result(log: &GameLog) -> &'static str {
+    match log.result {
+        Some((Some(Side::White), _)) => "1-0",
+        Some((Some(Side::Black), _)) => "0-1",
+        Some((None, GameOverReason::Aborted)) | Some((None, GameOverReason::Void)) | None => "*",
+        Some((None, _)) => "1/2-1/2",
+    }
+}
+
+pub fn summary(log: &GameLog) -> dotchess::Result<Summary> {
+    Ok(Summary {
+        game: game_id_hex(&log.game_id),
+        result: result(log),
+        winner: log.result.and_then(|(winner, _)| winner).map(side_name),
+        reason: log.result.map(|(_, reason)| reason.as_str()),
+        plies: log.plies.len(),
+        start_fen: log.start_fen.clone(),
+        final_fen: log.fen()?,
+        draw_offers: DrawOffers {
+            white: log.draw_offers[Side::White as usize],
+            black: log.draw_offers[Side::Black as usize],
+            white_declined: log.draw_offers_declined[Side::White as usize],
+            black_declined: log.draw_offers_declined[Side::Black as usize],
+        },
+        clocks: log
+            .clocks
+            .map(|(unit, white_time_left, black_time_left)| Clocks {
+                unit: match unit {
+                    ClockUnit::Blocks => "blocks",
+                    ClockUnit::Milliseconds => "milliseconds",
+                },
+                white_time_left,
+                black_time_left,
+            }),
+        inconsistencies: log
+            .inconsistencies
+            .iter()
+            .map(|inconsistency| InconsistencySummary {
+                ply: inconsistency.ply,
+                message: inconsistency.message.clone(),
+            })
+            .collect(),
+    })
+}
+
+/// Returns PGN representation of the game
+pub fn pgn(log: &GameLog) -> Result<String, std::fmt::Error> {
+    let mut pgn = String::new();
+    let result = result(log);
+
+    writeln!(&mut pgn, "[Event \"DotChess\"]")?;
+    writeln!(&mut pgn, "[Site \"{}\"]", game_id_hex(&log.game_id))?;
+
+    if log.start_fen != Game::FEN_NEW_GAME {
+        writeln!(&mut pgn, "[SetUp \"1\"]")?;
+        writeln!(&mut pgn, "[FEN \"{}\"]", log.start_fen)?;
+    }
+
+    if let Some((_, reason)) = log.result {
+        writeln!(&mut pgn, "[Termination \"{}\"]", reason.as_str())?;
+    }
+
+    writeln!(&mut pgn, "[Result \"{}\"]", result)?;
+    writeln!(&mut pgn)?;
+
+    for (index, ply) in log.plies.iter().enumerate() {
+        match ply.side {
+            Side::White => write!(&mut pgn, "{}. ", ply.fullmove_number)?,
+            Side::Black if index == 0 => write!(&mut pgn, "{}... ", ply.fullmove_number)?,
+            Side::Black => {}
+        }
+
+        write!(&mut pgn, "{} ", ply.san)?;
+    }
+
+    write!(&mut pgn, "{}", result)?;
+
+    Ok(pgn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Event;
+    use crate::replay::tests::board_updates;
+    use dotchess::DrawOfferChange;
+
+    #[test]
+    fn pgn_and_summary() {
+        let mut log = GameLog::new([0x01; 32], Game::FEN_NEW_GAME).unwrap();
+        let mut events = board_updates(&["e2e4", "e7e5", "d1h5"]);
+        events.push(Event::DrawOfferUpdate {
+            side: Side::White,
+            offer: true,
+            change: DrawOfferChange::Offered,
+        });
+        events.push(Event::GameOver {
+            winner: Some(Side::White),
+            reason: GameOverReason::Resignation,
+            handicap: None,
+        });
+
+        for event in &events {
+            log.apply(event);
+        }
+
+        assert!(pgn(&log)
+            .unwrap()
+            .ends_with("[Termination \"resignation\"]\n[Result \"1-0\"]\n\n1. e4 e5 2. Qh5 1-0"));
+
+        let summary = summary(&log).unwrap();
+
+        assert_eq!(summary.result, "1-0");
+        assert_eq!(summary.winner, Some("white"));
+        assert_eq!(summary.plies, 3);
+        assert_eq!(summary.draw_offers.white, 1);
+        assert!(summary.inconsistencies.is_empty());
+    }
+}
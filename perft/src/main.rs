@@ -3,7 +3,7 @@
 extern crate alloc;
 extern crate dotchess;
 
-use dotchess::{Error, Game, Mov, Result};
+use dotchess::{Game, Mov, Result};
 use std::{convert::TryInto, env};
 
 pub fn main() -> Result<()> {
@@ -13,11 +13,7 @@ pub fn main() -> Result<()> {
         .get(1)
         .expect("depth argument missing")
         .parse::<usize>()
-        .or_else(|_| {
-            Err(Error::InvalidArgument(String::from(
-                "depth must be a number",
-            )))
-        })?;
+        .expect("depth must be a number");
 
     assert!(depth > 0, "depth must be higher than 0");
 
@@ -0,0 +1,48 @@
+use scale::{Decode, Encode};
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Encode, Decode, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+    /// Caller is not allowed to perform the action
+    InvalidCaller,
+    /// Puzzle position is not a valid FEN or the game in it is already over
+    InvalidPosition,
+    /// Goal has no moves or more moves than can be verified
+    InvalidGoal,
+    /// Puzzle must be posted with a non-zero bounty
+    InvalidBounty,
+    /// Deadline must be in the future
+    InvalidDeadline,
+    /// There is no puzzle with given id
+    UnknownPuzzle,
+    /// Puzzle has been solved or its bounty reclaimed
+    PuzzleClosed,
+    /// Puzzle deadline has passed
+    DeadlinePassed,
+    /// Puzzle deadline has not passed yet
+    DeadlineNotReached,
+    /// Caller has not committed to a solution
+    NoCommitment,
+    /// Solution can be revealed only in a block after it was committed
+    RevealTooEarly,
+    /// Revealed solution does not match the commitment
+    CommitmentMismatch,
+    /// Revealed solution does not solve the puzzle
+    InvalidSolution,
+    /// Transfer of funds failed
+    TransferFailed,
+    Other,
+}
+
+impl core::convert::From<ink_env::Error> for Error {
+    fn from(error: ink_env::Error) -> Self {
+        match error {
+            ink_env::Error::TransferFailed | ink_env::Error::BelowSubsistenceThreshold => {
+                Self::TransferFailed
+            }
+            _ => Self::Other,
+        }
+    }
+}
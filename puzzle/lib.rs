@@ -0,0 +1,449 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod common;
+mod solver;
+
+pub use crate::common::{Error, Result};
+pub use crate::puzzle::{Puzzle, PuzzleBoard, PuzzleStatus};
+pub use crate::solver::{Goal, MAX_MOVES};
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod puzzle {
+
+    use crate::common::{Error, Result};
+    use crate::solver::{self, Goal, MAX_MOVES};
+    use alloc::string::String;
+    use dotchess::{Game, Mov};
+    use ink_env::hash::Blake2x256;
+    use ink_storage::collections::HashMap;
+    use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
+    use scale::{Decode, Encode};
+
+    /// Event emitted when the `sponsor` posts puzzle `id` with `bounty`
+    #[ink(event)]
+    pub struct PuzzlePosted {
+        #[ink(topic)]
+        id: u32,
+        #[ink(topic)]
+        sponsor: AccountId,
+        bounty: Balance,
+    }
+
+    /// Event emitted when the `solver` commits to a solution of puzzle `id`
+    #[ink(event)]
+    pub struct SolutionCommitted {
+        #[ink(topic)]
+        id: u32,
+        #[ink(topic)]
+        solver: AccountId,
+    }
+
+    /// Event emitted when the `solver` reveals valid solution of puzzle `id`
+    /// and receives the `bounty`
+    #[ink(event)]
+    pub struct PuzzleSolved {
+        #[ink(topic)]
+        id: u32,
+        #[ink(topic)]
+        solver: AccountId,
+        bounty: Balance,
+    }
+
+    /// Event emitted when the sponsor reclaims bounty of unsolved puzzle `id`
+    #[ink(event)]
+    pub struct BountyReclaimed {
+        #[ink(topic)]
+        id: u32,
+    }
+
+    /// Puzzle status
+    #[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(feature = "std", derive(Debug, scale_info::TypeInfo, StorageLayout))]
+    pub enum PuzzleStatus {
+        Open,
+        Solved { solver: AccountId },
+        Reclaimed,
+    }
+
+    /// Puzzle with `goal` for the side to move in position `fen`
+    ///
+    /// Solutions are accepted until `deadline` block, after that an unsolved
+    /// puzzle `bounty` can be reclaimed by the `sponsor`
+    #[derive(Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    pub struct Puzzle {
+        pub sponsor: AccountId,
+        pub fen: String,
+        pub goal: Goal,
+        pub bounty: Balance,
+        pub deadline: BlockNumber,
+        pub status: PuzzleStatus,
+    }
+
+    #[ink(storage)]
+    pub struct PuzzleBoard {
+        /// Posted puzzles, puzzle id is its index
+        puzzles: HashMap<u32, Puzzle>,
+        /// Number of posted puzzles
+        puzzle_count: u32,
+        /// Solution hash and commitment block by puzzle id and solver
+        commitments: HashMap<(u32, AccountId), ([u8; 32], BlockNumber)>,
+    }
+
+    impl PuzzleBoard {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                puzzles: HashMap::new(),
+                puzzle_count: 0,
+                commitments: HashMap::new(),
+            }
+        }
+
+        /// Posts puzzle with `goal` for the side to move in position `fen`,
+        /// transferred balance is the bounty. Returns id of the puzzle.
+        #[ink(message, payable)]
+        pub fn post(&mut self, fen: String, goal: Goal, deadline: BlockNumber) -> Result<u32> {
+            let game = Game::new(&fen).map_err(|_| Error::InvalidPosition)?;
+
+            if !game.has_legal_moves() {
+                return Err(Error::InvalidPosition);
+            }
+
+            if goal.moves() == 0 || goal.moves() > MAX_MOVES {
+                return Err(Error::InvalidGoal);
+            }
+
+            let bounty = self.env().transferred_balance();
+
+            if bounty == 0 {
+                return Err(Error::InvalidBounty);
+            }
+
+            if deadline <= self.env().block_number() {
+                return Err(Error::InvalidDeadline);
+            }
+
+            let id = self.puzzle_count;
+            let sponsor = self.env().caller();
+
+            self.puzzles.insert(
+                id,
+                Puzzle {
+                    sponsor,
+                    fen,
+                    goal,
+                    bounty,
+                    deadline,
+                    status: PuzzleStatus::Open,
+                },
+            );
+            self.puzzle_count += 1;
+
+            self.env().emit_event(PuzzlePosted {
+                id,
+                sponsor,
+                bounty,
+            });
+
+            Ok(id)
+        }
+
+        /// Returns puzzle `id`
+        #[ink(message)]
+        pub fn puzzle(&self, id: u32) -> Option<Puzzle> {
+            self.puzzles.get(&id).cloned()
+        }
+
+        /// Returns number of posted puzzles
+        #[ink(message)]
+        pub fn puzzle_count(&self) -> u32 {
+            self.puzzle_count
+        }
+
+        /// Commits the caller to a solution of puzzle `id`, `hash` is the
+        /// `solution_hash` of the solution
+        ///
+        /// The solution can be revealed in any later block, committing again
+        /// replaces the previous commitment
+        #[ink(message)]
+        pub fn commit(&mut self, id: u32, hash: [u8; 32]) -> Result<()> {
+            self.open_puzzle(id)?;
+
+            let solver = self.env().caller();
+
+            self.commitments
+                .insert((id, solver), (hash, self.env().block_number()));
+
+            self.env().emit_event(SolutionCommitted { id, solver });
+
+            Ok(())
+        }
+
+        /// Reveals solution `moves` of puzzle `id` committed by the caller,
+        /// the first valid solution receives the bounty
+        #[ink(message)]
+        pub fn reveal(
+            &mut self,
+            id: u32,
+            moves: alloc::vec::Vec<Mov>,
+            salt: [u8; 32],
+        ) -> Result<()> {
+            let puzzle = self.open_puzzle(id)?;
+            let solver = self.env().caller();
+
+            let (hash, block) = *self
+                .commitments
+                .get(&(id, solver))
+                .ok_or(Error::NoCommitment)?;
+
+            if block >= self.env().block_number() {
+                return Err(Error::RevealTooEarly);
+            }
+
+            if hash != self.solution_hash(id, solver, moves.clone(), salt) {
+                return Err(Error::CommitmentMismatch);
+            }
+
+            let game = Game::new(&puzzle.fen).map_err(|_| Error::InvalidPosition)?;
+
+            if !solver::verify(&game, puzzle.goal, &moves) {
+                return Err(Error::InvalidSolution);
+            }
+
+            self.env().transfer(solver, puzzle.bounty)?;
+
+            self.set_status(id, PuzzleStatus::Solved { solver });
+            self.commitments.take(&(id, solver));
+
+            self.env().emit_event(PuzzleSolved {
+                id,
+                solver,
+                bounty: puzzle.bounty,
+            });
+
+            Ok(())
+        }
+
+        /// Returns bounty of unsolved puzzle `id` to its sponsor after the deadline
+        #[ink(message)]
+        pub fn reclaim(&mut self, id: u32) -> Result<()> {
+            let puzzle = self.puzzle(id).ok_or(Error::UnknownPuzzle)?;
+
+            if puzzle.status != PuzzleStatus::Open {
+                return Err(Error::PuzzleClosed);
+            }
+
+            if self.env().caller() != puzzle.sponsor {
+                return Err(Error::InvalidCaller);
+            }
+
+            if self.env().block_number() <= puzzle.deadline {
+                return Err(Error::DeadlineNotReached);
+            }
+
+            self.env().transfer(puzzle.sponsor, puzzle.bounty)?;
+
+            self.set_status(id, PuzzleStatus::Reclaimed);
+            self.env().emit_event(BountyReclaimed { id });
+
+            Ok(())
+        }
+
+        /// Returns hash `solver` commits to before revealing solution `moves`
+        /// of puzzle `id`, `salt` keeps short solutions from being guessed
+        #[ink(message)]
+        pub fn solution_hash(
+            &self,
+            id: u32,
+            solver: AccountId,
+            moves: alloc::vec::Vec<Mov>,
+            salt: [u8; 32],
+        ) -> [u8; 32] {
+            self.env().hash_encoded::<Blake2x256, _>(&(
+                b"puzzle",
+                self.env().account_id(),
+                id,
+                solver,
+                moves,
+                salt,
+            ))
+        }
+
+        /// Returns puzzle `id` if it accepts solutions
+        fn open_puzzle(&self, id: u32) -> Result<Puzzle> {
+            let puzzle = self.puzzle(id).ok_or(Error::UnknownPuzzle)?;
+
+            if puzzle.status != PuzzleStatus::Open {
+                return Err(Error::PuzzleClosed);
+            }
+
+            if self.env().block_number() > puzzle.deadline {
+                return Err(Error::DeadlinePassed);
+            }
+
+            Ok(puzzle)
+        }
+
+        fn set_status(&mut self, id: u32, status: PuzzleStatus) {
+            if let Some(puzzle) = self.puzzles.get_mut(&id) {
+                puzzle.status = status;
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use core::convert::TryInto;
+        use ink_env::DefaultEnvironment;
+        use ink_lang as ink;
+
+        const MATE_IN_TWO: &str = "7k/8/8/8/8/8/R7/1R4K1 w - - 0 1";
+
+        fn moves(moves: &[&str]) -> alloc::vec::Vec<Mov> {
+            moves.iter().map(|mov| (*mov).try_into().unwrap()).collect()
+        }
+
+        fn post(board: &mut PuzzleBoard, sponsor: AccountId, bounty: Balance) -> u32 {
+            set_caller(sponsor, bounty);
+
+            // Off-chain environment does not move transferred value
+            let contract = ink_env::account_id::<DefaultEnvironment>().unwrap();
+            ink_env::test::set_account_balance::<DefaultEnvironment>(contract, bounty).unwrap();
+
+            board
+                .post(MATE_IN_TWO.into(), Goal::MateIn { moves: 2 }, 10)
+                .unwrap()
+        }
+
+        #[ink::test]
+        fn post_validates_puzzle() {
+            let accounts = default_accounts();
+            let mut board = PuzzleBoard::new();
+
+            set_caller(accounts.alice, 100);
+
+            assert_eq!(
+                board.post("8/8".into(), Goal::MateIn { moves: 1 }, 10),
+                Err(Error::InvalidPosition)
+            );
+            assert_eq!(
+                board.post(MATE_IN_TWO.into(), Goal::MateIn { moves: 3 }, 10),
+                Err(Error::InvalidGoal)
+            );
+            assert_eq!(
+                board.post(MATE_IN_TWO.into(), Goal::MateIn { moves: 2 }, 0),
+                Err(Error::InvalidDeadline)
+            );
+
+            set_caller(accounts.alice, 0);
+
+            assert_eq!(
+                board.post(MATE_IN_TWO.into(), Goal::MateIn { moves: 2 }, 10),
+                Err(Error::InvalidBounty)
+            );
+            assert_eq!(board.puzzle_count(), 0);
+        }
+
+        #[ink::test]
+        fn first_valid_solver_gets_bounty() {
+            let accounts = default_accounts();
+            let mut board = PuzzleBoard::new();
+            let id = post(&mut board, accounts.alice, 100);
+
+            let wrong = moves(&["b1b8", "h8g7"]);
+            let solution = moves(&["a2a7", "h8g8", "b1b8"]);
+            let salt = [0x07; 32];
+
+            set_caller(accounts.bob, 0);
+            let hash = board.solution_hash(id, accounts.bob, wrong.clone(), salt);
+            board.commit(id, hash).unwrap();
+
+            set_caller(accounts.charlie, 0);
+            let hash = board.solution_hash(id, accounts.charlie, solution.clone(), salt);
+            board.commit(id, hash).unwrap();
+
+            assert_eq!(
+                board.reveal(id, solution.clone(), salt),
+                Err(Error::RevealTooEarly)
+            );
+
+            advance_block();
+
+            set_caller(accounts.bob, 0);
+            assert_eq!(
+                board.reveal(id, solution.clone(), salt),
+                Err(Error::CommitmentMismatch)
+            );
+            assert_eq!(board.reveal(id, wrong, salt), Err(Error::InvalidSolution));
+
+            set_caller(accounts.charlie, 0);
+            let balance = balance_of(accounts.charlie);
+            board.reveal(id, solution.clone(), salt).unwrap();
+
+            assert_eq!(balance_of(accounts.charlie), balance + 100);
+            assert_eq!(
+                board.puzzle(id).unwrap().status,
+                PuzzleStatus::Solved {
+                    solver: accounts.charlie
+                }
+            );
+            assert_eq!(board.reveal(id, solution, salt), Err(Error::PuzzleClosed));
+        }
+
+        #[ink::test]
+        fn sponsor_reclaims_unsolved_bounty() {
+            let accounts = default_accounts();
+            let mut board = PuzzleBoard::new();
+            let id = post(&mut board, accounts.alice, 100);
+
+            set_caller(accounts.alice, 0);
+            assert_eq!(board.reclaim(id), Err(Error::DeadlineNotReached));
+
+            for _ in 0..11 {
+                advance_block();
+            }
+
+            set_caller(accounts.bob, 0);
+            assert_eq!(board.reclaim(id), Err(Error::InvalidCaller));
+            assert_eq!(board.commit(id, [0x00; 32]), Err(Error::DeadlinePassed));
+
+            set_caller(accounts.alice, 0);
+            let balance = balance_of(accounts.alice);
+            board.reclaim(id).unwrap();
+
+            assert_eq!(balance_of(accounts.alice), balance + 100);
+            assert_eq!(board.reclaim(id), Err(Error::PuzzleClosed));
+        }
+
+        fn default_accounts() -> ink_env::test::DefaultAccounts<DefaultEnvironment> {
+            ink_env::test::default_accounts::<DefaultEnvironment>().unwrap()
+        }
+
+        fn balance_of(account: AccountId) -> Balance {
+            ink_env::test::get_account_balance::<DefaultEnvironment>(account).unwrap()
+        }
+
+        fn set_caller(caller: AccountId, transferred: Balance) {
+            ink_env::test::push_execution_context::<DefaultEnvironment>(
+                caller,
+                ink_env::account_id::<DefaultEnvironment>().unwrap(),
+                1000000,
+                transferred,
+                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])),
+            );
+        }
+
+        fn advance_block() {
+            ink_env::test::advance_block::<DefaultEnvironment>().unwrap();
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Verification of puzzle solutions
+//!
+//! A solution is a line of moves starting with a move of the side to move in the
+//! puzzle position. The line is followed move by move, every defender reply which
+//! is not in the line is refuted by exhaustive search, so the line only has to
+//! cover the main variation. The line may end early, the rest is searched.
+
+use dotchess::{Game, Mov, Piece, Side, Square};
+use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
+use scale::{Decode, Encode};
+
+/// Maximum number of moves of the solving side, verification cost grows
+/// exponentially with the number of moves and deeper puzzles would not fit
+/// into the block gas limit
+pub const MAX_MOVES: u8 = 2;
+
+/// Goal of the side to move in the puzzle position
+#[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+#[cfg_attr(
+    feature = "std",
+    derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+)]
+pub enum Goal {
+    /// Checkmate in at most `moves` moves against any defence
+    MateIn { moves: u8 },
+    /// Gain at least `material` within `moves` moves against any defence, pawns
+    /// are worth 1, knights and bishops 3, rooks 5 and queens 9. Checkmate also counts.
+    WinMaterial { moves: u8, material: u8 },
+}
+
+impl Goal {
+    pub fn moves(&self) -> u8 {
+        match self {
+            Goal::MateIn { moves } => *moves,
+            Goal::WinMaterial { moves, .. } => *moves,
+        }
+    }
+}
+
+/// Returns true if `line` solves puzzle with `goal` in position of `game`,
+/// the line has to contain at least the first move
+pub fn verify(game: &Game, goal: Goal, line: &[Mov]) -> bool {
+    if line.is_empty() {
+        return false;
+    }
+
+    let attacker = game.side_next_in_turn();
+
+    let verifier = Verifier {
+        goal,
+        attacker,
+        material: material(game, attacker),
+    };
+
+    verifier.attack(game, goal.moves(), Some(line))
+}
+
+struct Verifier {
+    goal: Goal,
+    attacker: Side,
+    /// Material balance of the attacker in the puzzle position
+    material: i32,
+}
+
+impl Verifier {
+    /// Returns true if the attacker to move in `game` reaches the goal in `moves`
+    /// moves, playing `line` if given or searching all moves once the line ends
+    fn attack(&self, game: &Game, moves: u8, line: Option<&[Mov]>) -> bool {
+        if moves == 0 {
+            return false;
+        }
+
+        match line.and_then(|line| line.split_first()) {
+            Some((mov, rest)) => self.attack_with(game, mov, moves, Some(rest)),
+            None => game
+                .legal_moves()
+                .iter()
+                .any(|mov| self.attack_with(game, mov, moves, None)),
+        }
+    }
+
+    fn attack_with(&self, game: &Game, mov: &Mov, moves: u8, line: Option<&[Mov]>) -> bool {
+        let game = match game.make_move(mov) {
+            Ok(game) => game,
+            Err(_) => return false,
+        };
+
+        let is_mate_goal = matches!(self.goal, Goal::MateIn { .. });
+
+        // Last move must mate, which it cannot without giving check
+        if is_mate_goal && moves == 1 && !game.is_check() {
+            return false;
+        }
+
+        if !game.has_legal_moves() {
+            // Stalemate does not solve any puzzle
+            return game.is_check() && line_ends(line);
+        }
+
+        if is_mate_goal && moves == 1 {
+            return false;
+        }
+
+        self.defend(&game, moves, line)
+    }
+
+    /// Returns true if every reply of the defender to move in `game` loses
+    fn defend(&self, game: &Game, moves: u8, line: Option<&[Mov]>) -> bool {
+        let (line_reply, rest) = match line.and_then(|line| line.split_first()) {
+            Some((reply, rest)) => (Some(*reply), Some(rest)),
+            None => (None, None),
+        };
+
+        let mut line_reply_legal = line_reply.is_none();
+
+        for reply in game.legal_moves().iter() {
+            let game = match game.make_move(reply) {
+                Ok(game) => game,
+                Err(_) => return false,
+            };
+
+            let in_line = line_reply == Some(*reply);
+            let rest = if in_line { rest } else { None };
+
+            line_reply_legal |= in_line;
+
+            if self.material_won(&game) {
+                if !line_ends(rest) {
+                    return false;
+                }
+
+                continue;
+            }
+
+            if !self.attack(&game, moves - 1, rest) {
+                return false;
+            }
+        }
+
+        line_reply_legal
+    }
+
+    fn material_won(&self, game: &Game) -> bool {
+        match self.goal {
+            Goal::WinMaterial {
+                material: target, ..
+            } => material(game, self.attacker) - self.material >= target as i32,
+            Goal::MateIn { .. } => false,
+        }
+    }
+}
+
+fn line_ends(line: Option<&[Mov]>) -> bool {
+    line.map_or(true, |line| line.is_empty())
+}
+
+/// Returns material of the `side` minus material of its opponent
+fn material(game: &Game, side: Side) -> i32 {
+    (0..64u8)
+        .filter_map(|square| game.piece_at(Square::from(square)))
+        .map(|(owner, piece)| {
+            let value = match piece {
+                Piece::Pawn => 1,
+                Piece::Knight | Piece::Bishop => 3,
+                Piece::Rook => 5,
+                Piece::Queen => 9,
+                Piece::King => 0,
+            };
+
+            if owner == side {
+                value
+            } else {
+                -value
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::TryInto;
+
+    fn line(moves: &[&str]) -> alloc::vec::Vec<Mov> {
+        moves.iter().map(|mov| (*mov).try_into().unwrap()).collect()
+    }
+
+    #[test]
+    fn mate_in_one() {
+        let game = Game::new("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let goal = Goal::MateIn { moves: 1 };
+
+        assert!(verify(&game, goal, &line(&["a1a8"])));
+        assert!(!verify(&game, goal, &line(&["a1a7"])));
+        assert!(!verify(&game, goal, &line(&["a1a8", "g8h8"])));
+        assert!(!verify(&game, goal, &[]));
+    }
+
+    #[test]
+    fn mate_in_two() {
+        let game = Game::new("7k/8/8/8/8/8/R7/1R4K1 w - - 0 1").unwrap();
+        let goal = Goal::MateIn { moves: 2 };
+
+        assert!(verify(&game, goal, &line(&["a2a7", "h8g8", "b1b8"])));
+
+        // Defence and mating move are searched
+        assert!(verify(&game, goal, &line(&["a2a7"])));
+        assert!(verify(&game, goal, &line(&["a2a7", "h8g8"])));
+
+        // Illegal defender reply
+        assert!(!verify(&game, goal, &line(&["a2a7", "h8h7", "b1b8"])));
+
+        // Checks let the king escape
+        assert!(!verify(&game, goal, &line(&["b1b8", "h8g7"])));
+        assert!(!verify(&game, Goal::MateIn { moves: 1 }, &line(&["a2a7"])));
+    }
+
+    #[test]
+    fn win_material() {
+        let game = Game::new("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1").unwrap();
+
+        let goal = Goal::WinMaterial {
+            moves: 1,
+            material: 9,
+        };
+
+        assert!(verify(&game, goal, &line(&["d1d5"])));
+        assert!(!verify(&game, goal, &line(&["d1d2"])));
+
+        let goal = Goal::WinMaterial {
+            moves: 1,
+            material: 10,
+        };
+
+        assert!(!verify(&game, goal, &line(&["d1d5"])));
+    }
+}
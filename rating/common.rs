@@ -0,0 +1,27 @@
+use scale::{Decode, Encode};
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Encode, Decode, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+    /// Caller is not allowed to perform the action
+    InvalidCaller,
+    /// Game contract code hash was not authorized by the owner
+    UnauthorizedCodeHash,
+    /// Game was not created by this registry
+    UnknownGame,
+    /// Game result has already been recorded
+    GameAlreadyReported,
+    /// Opponent of the player who created the game has not accepted it yet
+    GameNotAccepted,
+    /// Game has already been accepted by both players
+    GameAlreadyAccepted,
+    /// Game has not ended yet
+    GameNotOver,
+    /// Game was aborted before it started or is an odds game and is not rated
+    GameNotRated,
+    /// Game contract could not be instantiated
+    InstantiationFailed,
+    Other,
+}
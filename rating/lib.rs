@@ -0,0 +1,565 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod common;
+mod math;
+
+pub use crate::common::{Error, Result};
+pub use crate::rating::{PlayerRating, Rating, RatingSystem};
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod rating {
+
+    use crate::common::{Error, Result};
+    use crate::math;
+    use alloc::string::String;
+    use dotchess::{ClockUnit, DotChess, GameOverReason, Side, Status, TimeControl};
+    use ink_storage::collections::HashMap;
+    use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
+    use ink_storage::{Pack, Vec};
+    use scale::{Decode, Encode};
+
+    const DEFAULT_RATING: u32 = 1500;
+
+    /// Event emitted when rating of the `player` changes after the `game` was reported
+    #[ink(event)]
+    pub struct RatingUpdate {
+        #[ink(topic)]
+        player: AccountId,
+        #[ink(topic)]
+        game: AccountId,
+        rating: u32,
+        deviation: u32,
+    }
+
+    /// Event emitted when a new `game` contract is instantiated by the registry
+    #[ink(event)]
+    pub struct GameCreated {
+        #[ink(topic)]
+        game: AccountId,
+        white: AccountId,
+        black: AccountId,
+    }
+
+    /// Event emitted when the opponent of the player who created the `game` accepts it
+    #[ink(event)]
+    pub struct GameAccepted {
+        #[ink(topic)]
+        game: AccountId,
+    }
+
+    /// Rating system used by the registry
+    #[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    pub enum RatingSystem {
+        /// Elo rating, winner takes `k_factor` times the unexpected part of the result
+        Elo { k_factor: u32 },
+        /// Glicko-2 rating, each game is treated as a single rating period
+        Glicko2,
+    }
+
+    /// Rating of a player
+    ///
+    /// `deviation` and `volatility` (in millionths) are only updated by the Glicko-2 system
+    #[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    pub struct PlayerRating {
+        pub rating: u32,
+        pub deviation: u32,
+        pub volatility: u32,
+        pub games: u32,
+    }
+
+    impl Default for PlayerRating {
+        fn default() -> Self {
+            PlayerRating {
+                rating: DEFAULT_RATING,
+                deviation: math::MAX_DEVIATION,
+                volatility: math::DEFAULT_VOLATILITY,
+                games: 0,
+            }
+        }
+    }
+
+    #[ink(storage)]
+    pub struct Rating {
+        /// Account allowed to authorize game code hashes
+        owner: AccountId,
+        /// Rating system
+        system: Pack<RatingSystem>,
+        /// Game contract code hashes games can be created from
+        code_hashes: HashMap<Hash, ()>,
+        /// Games created by the registry, true once the result was recorded
+        games: HashMap<AccountId, bool>,
+        /// Games not accepted yet, mapped to the opponent who has to accept them
+        pending: HashMap<AccountId, AccountId>,
+        /// Player ratings
+        ratings: HashMap<AccountId, PlayerRating>,
+        /// Rated players
+        players: Vec<AccountId>,
+    }
+
+    impl Rating {
+        /// Initiates registry rating players by `system`, caller becomes the owner
+        #[ink(constructor)]
+        pub fn new(system: RatingSystem) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                system: Pack::new(system),
+                code_hashes: HashMap::new(),
+                games: HashMap::new(),
+                pending: HashMap::new(),
+                ratings: HashMap::new(),
+                players: Vec::new(),
+            }
+        }
+
+        /// Initiates registry rating players by Elo with K-factor of 32
+        #[ink(constructor)]
+        pub fn default() -> Self {
+            Self::new(RatingSystem::Elo { k_factor: 32 })
+        }
+
+        /// Rating system used by the registry
+        #[ink(message)]
+        pub fn system(&self) -> RatingSystem {
+            *self.system
+        }
+
+        /// Allows games to be created from the DotChess contract `code_hash`
+        #[ink(message)]
+        pub fn authorize_code_hash(&mut self, code_hash: Hash) -> Result<()> {
+            self.ensure_owner()?;
+            self.code_hashes.insert(code_hash, ());
+
+            Ok(())
+        }
+
+        /// Disallows games to be created from the DotChess contract `code_hash`
+        #[ink(message)]
+        pub fn revoke_code_hash(&mut self, code_hash: Hash) -> Result<()> {
+            self.ensure_owner()?;
+            self.code_hashes.take(&code_hash);
+
+            Ok(())
+        }
+
+        /// Returns true if games can be created from `code_hash`
+        #[ink(message)]
+        pub fn is_authorized(&self, code_hash: Hash) -> bool {
+            self.code_hashes.contains_key(&code_hash)
+        }
+
+        /// Instantiates a rated game from the authorized `code_hash`, transferred
+        /// balance is passed on as the game contract endowment
+        ///
+        /// Caller has to be one of the players, the game is not rated
+        /// until the other player accepts it by `accept_game`
+        ///
+        /// Returns account of the new game contract
+        #[ink(message, payable)]
+        #[allow(clippy::too_many_arguments)]
+        pub fn create_game(
+            &mut self,
+            code_hash: Hash,
+            white: AccountId,
+            black: AccountId,
+            time_control: TimeControl,
+            clock_unit: ClockUnit,
+            stake: Balance,
+            fen: String,
+            salt: [u8; 4],
+        ) -> Result<AccountId> {
+            if !self.is_authorized(code_hash) {
+                return Err(Error::UnauthorizedCodeHash);
+            }
+
+            let caller = self.env().caller();
+            let opponent = if caller == white {
+                black
+            } else if caller == black {
+                white
+            } else {
+                return Err(Error::InvalidCaller);
+            };
+
+            let game = DotChess::with_stake(white, black, time_control, clock_unit, stake, fen)
+                .endowment(self.env().transferred_balance())
+                .code_hash(code_hash)
+                .salt_bytes(salt)
+                .instantiate()
+                .map_err(|_| Error::InstantiationFailed)?;
+
+            let account = ink_lang::ToAccountId::to_account_id(&game);
+
+            self.games.insert(account, false);
+            self.pending.insert(account, opponent);
+            self.env().emit_event(GameCreated {
+                game: account,
+                white,
+                black,
+            });
+
+            Ok(account)
+        }
+
+        /// Agrees to have the `game` created by the opponent rated
+        #[ink(message)]
+        pub fn accept_game(&mut self, game: AccountId) -> Result<()> {
+            match self.pending.get(&game) {
+                Some(opponent) if *opponent == self.env().caller() => {}
+                Some(_) => return Err(Error::InvalidCaller),
+                None if self.games.contains_key(&game) => return Err(Error::GameAlreadyAccepted),
+                None => return Err(Error::UnknownGame),
+            }
+
+            self.pending.take(&game);
+            self.env().emit_event(GameAccepted { game });
+
+            Ok(())
+        }
+
+        /// Returns true if the `game` was accepted by both players
+        #[ink(message)]
+        pub fn is_accepted(&self, game: AccountId) -> bool {
+            self.games.contains_key(&game) && !self.pending.contains_key(&game)
+        }
+
+        /// Records result of the finished `game` and updates ratings of both players
+        ///
+        /// Anyone can report a game accepted by both players, but each game is rated
+        /// only once. Odds games, aborted and voided games are not rated.
+        #[ink(message)]
+        pub fn report_game(&mut self, game: AccountId) -> Result<()> {
+            match self.games.get(&game) {
+                None => return Err(Error::UnknownGame),
+                Some(true) => return Err(Error::GameAlreadyReported),
+                Some(false) => {}
+            }
+
+            if self.pending.contains_key(&game) {
+                return Err(Error::GameNotAccepted);
+            }
+
+            let contract: DotChess = ink_env::call::FromAccountId::from_account_id(game);
+
+            if contract.handicap().is_some() {
+                return Err(Error::GameNotRated);
+            }
+
+            let winner = match contract.status() {
+                Status::Finished {
+                    reason: GameOverReason::Aborted,
+                    ..
+                }
+                | Status::Finished {
+                    reason: GameOverReason::Void,
+                    ..
+                } => return Err(Error::GameNotRated),
+                Status::Finished { winner, .. } => winner,
+                _ => return Err(Error::GameNotOver),
+            };
+
+            let (white, black) = contract.players();
+
+            self.games.insert(game, true);
+            self.record_result(game, white, black, winner);
+
+            Ok(())
+        }
+
+        /// Returns true if result of the `game` has been recorded
+        #[ink(message)]
+        pub fn is_reported(&self, game: AccountId) -> bool {
+            self.games.get(&game).copied().unwrap_or(false)
+        }
+
+        /// Rating of the `player`, players without rated games have the default rating
+        #[ink(message)]
+        pub fn rating_of(&self, player: AccountId) -> PlayerRating {
+            self.ratings.get(&player).copied().unwrap_or_default()
+        }
+
+        /// Returns at most `limit` players sorted by rating from the highest,
+        /// skipping the first `offset` of them
+        #[ink(message)]
+        pub fn leaderboard(
+            &self,
+            offset: u32,
+            limit: u32,
+        ) -> alloc::vec::Vec<(AccountId, PlayerRating)> {
+            let mut players: alloc::vec::Vec<(AccountId, PlayerRating)> = self
+                .players
+                .iter()
+                .map(|player| (*player, self.rating_of(*player)))
+                .collect();
+
+            players.sort_by_key(|(_, player)| core::cmp::Reverse(player.rating));
+
+            players
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect()
+        }
+
+        fn record_result(
+            &mut self,
+            game: AccountId,
+            white: AccountId,
+            black: AccountId,
+            winner: Option<Side>,
+        ) {
+            let (white_score, black_score) = match winner {
+                Some(Side::White) => (math::WIN, math::LOSS),
+                Some(Side::Black) => (math::LOSS, math::WIN),
+                None => (math::DRAW, math::DRAW),
+            };
+
+            let white_rating = self.rating_of(white);
+            let black_rating = self.rating_of(black);
+
+            let (white_new, black_new) = match *self.system {
+                RatingSystem::Elo { k_factor } => (
+                    PlayerRating {
+                        rating: math::elo(
+                            white_rating.rating,
+                            black_rating.rating,
+                            white_score,
+                            k_factor,
+                        ),
+                        ..white_rating
+                    },
+                    PlayerRating {
+                        rating: math::elo(
+                            black_rating.rating,
+                            white_rating.rating,
+                            black_score,
+                            k_factor,
+                        ),
+                        ..black_rating
+                    },
+                ),
+                RatingSystem::Glicko2 => {
+                    let (white_r, white_rd, white_vol) = math::glicko2(
+                        white_rating.rating,
+                        white_rating.deviation,
+                        white_rating.volatility,
+                        &[(black_rating.rating, black_rating.deviation, white_score)],
+                    );
+                    let (black_r, black_rd, black_vol) = math::glicko2(
+                        black_rating.rating,
+                        black_rating.deviation,
+                        black_rating.volatility,
+                        &[(white_rating.rating, white_rating.deviation, black_score)],
+                    );
+
+                    (
+                        PlayerRating {
+                            rating: white_r,
+                            deviation: white_rd,
+                            volatility: white_vol,
+                            ..white_rating
+                        },
+                        PlayerRating {
+                            rating: black_r,
+                            deviation: black_rd,
+                            volatility: black_vol,
+                            ..black_rating
+                        },
+                    )
+                }
+            };
+
+            self.update_player(game, white, white_new);
+            self.update_player(game, black, black_new);
+        }
+
+        fn update_player(&mut self, game: AccountId, player: AccountId, rating: PlayerRating) {
+            let rating = PlayerRating {
+                games: rating.games + 1,
+                ..rating
+            };
+
+            if self.ratings.insert(player, rating).is_none() {
+                self.players.push(player);
+            }
+
+            self.env().emit_event(RatingUpdate {
+                player,
+                game,
+                rating: rating.rating,
+                deviation: rating.deviation,
+            });
+        }
+
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() == self.owner {
+                Ok(())
+            } else {
+                Err(Error::InvalidCaller)
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_lang as ink;
+
+        fn accounts() -> ink_env::test::DefaultAccounts<ink_env::DefaultEnvironment> {
+            ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().unwrap()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
+                caller,
+                AccountId::from([0x07; 32]),
+                1000000,
+                0,
+                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])),
+            );
+        }
+
+        #[ink::test]
+        fn unrated_player_has_default_rating() {
+            let rating = Rating::default();
+
+            assert_eq!(
+                rating.rating_of(accounts().alice),
+                PlayerRating {
+                    rating: 1500,
+                    deviation: 350,
+                    volatility: 60_000,
+                    games: 0
+                }
+            );
+            assert!(rating.leaderboard(0, 10).is_empty());
+        }
+
+        #[ink::test]
+        fn elo_result_updates_both_players() {
+            let accounts = accounts();
+            let mut rating = Rating::default();
+            let game = AccountId::from([0x10; 32]);
+
+            rating.record_result(game, accounts.alice, accounts.bob, Some(Side::White));
+
+            assert_eq!(rating.rating_of(accounts.alice).rating, 1516);
+            assert_eq!(rating.rating_of(accounts.alice).games, 1);
+            assert_eq!(rating.rating_of(accounts.bob).rating, 1484);
+
+            rating.record_result(game, accounts.charlie, accounts.bob, None);
+
+            let leaderboard = rating.leaderboard(0, 10);
+
+            assert_eq!(leaderboard.len(), 3);
+            assert_eq!(leaderboard[0].0, accounts.alice);
+            assert_eq!(leaderboard[2].0, accounts.bob);
+            assert_eq!(rating.leaderboard(1, 1)[0].0, accounts.charlie);
+        }
+
+        #[ink::test]
+        fn glicko2_result_updates_deviation() {
+            let accounts = accounts();
+            let mut rating = Rating::new(RatingSystem::Glicko2);
+            let game = AccountId::from([0x10; 32]);
+
+            rating.record_result(game, accounts.alice, accounts.bob, Some(Side::Black));
+
+            assert_eq!(rating.rating_of(accounts.alice).rating, 1338);
+            assert_eq!(rating.rating_of(accounts.alice).deviation, 290);
+            assert_eq!(rating.rating_of(accounts.bob).rating, 1662);
+            assert_eq!(rating.rating_of(accounts.bob).volatility, 60_000);
+        }
+
+        #[ink::test]
+        fn only_owner_authorizes_code_hashes() {
+            let mut rating = Rating::default();
+            let code_hash = Hash::from([0x42; 32]);
+
+            assert_eq!(rating.authorize_code_hash(code_hash), Ok(()));
+            assert!(rating.is_authorized(code_hash));
+
+            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
+                accounts().bob,
+                AccountId::from([0x07; 32]),
+                1000000,
+                0,
+                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])),
+            );
+
+            assert_eq!(
+                rating.revoke_code_hash(code_hash),
+                Err(Error::InvalidCaller)
+            );
+            assert!(rating.is_authorized(code_hash));
+        }
+
+        #[ink::test]
+        fn unknown_game_cannot_be_reported() {
+            let mut rating = Rating::default();
+
+            assert_eq!(
+                rating.report_game(AccountId::from([0x10; 32])),
+                Err(Error::UnknownGame)
+            );
+        }
+
+        #[ink::test]
+        fn only_players_create_games() {
+            let accounts = accounts();
+            let mut rating = Rating::default();
+            let code_hash = Hash::from([0x42; 32]);
+
+            rating.authorize_code_hash(code_hash).unwrap();
+            set_caller(accounts.charlie);
+
+            assert_eq!(
+                rating.create_game(
+                    code_hash,
+                    accounts.alice,
+                    accounts.bob,
+                    TimeControl::Correspondence { per_move: 10 },
+                    ClockUnit::Blocks,
+                    0,
+                    String::from(dotchess::Game::FEN_NEW_GAME),
+                    [0; 4],
+                ),
+                Err(Error::InvalidCaller)
+            );
+        }
+
+        #[ink::test]
+        fn game_is_rated_once_accepted_by_opponent() {
+            let accounts = accounts();
+            let mut rating = Rating::default();
+            let game = AccountId::from([0x10; 32]);
+
+            rating.games.insert(game, false);
+            rating.pending.insert(game, accounts.bob);
+
+            assert!(!rating.is_accepted(game));
+            assert_eq!(rating.report_game(game), Err(Error::GameNotAccepted));
+
+            set_caller(accounts.charlie);
+
+            assert_eq!(rating.accept_game(game), Err(Error::InvalidCaller));
+
+            set_caller(accounts.bob);
+
+            assert_eq!(rating.accept_game(game), Ok(()));
+            assert!(rating.is_accepted(game));
+            assert_eq!(rating.accept_game(game), Err(Error::GameAlreadyAccepted));
+        }
+    }
+}
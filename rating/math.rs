@@ -0,0 +1,343 @@
+//! Fixed-point implementation of Elo and Glicko-2 rating updates
+//!
+//! Contracts cannot use floating point instructions, so all the math is done
+//! on `Fixed` numbers scaled by `ONE`.
+
+pub type Fixed = i128;
+
+pub const ONE: Fixed = 1_000_000_000_000;
+
+const LN2: Fixed = 693_147_180_560;
+const LN10: Fixed = 2_302_585_092_994;
+const PI_SQUARED: Fixed = 9_869_604_401_089;
+
+/// Glicko-2 scale factor 400 / ln(10) converting ratings to the Glicko-2 scale
+const SCALE: Fixed = 173_717_792_761_301;
+
+/// Glicko-2 system constant τ constraining the change in volatility over time
+const TAU: Fixed = ONE / 2;
+
+/// Convergence tolerance of the volatility iteration
+const EPSILON: Fixed = ONE / 1_000_000;
+
+/// Upper bound on iterations of the volatility computation
+const MAX_ITERATIONS: u32 = 50;
+
+/// Rating at the center of the Glicko-2 scale
+pub const CENTER_RATING: u32 = 1500;
+
+/// Lowest rating deviation a player can reach
+pub const MIN_DEVIATION: u32 = 30;
+
+/// Rating deviation of an unrated player
+pub const MAX_DEVIATION: u32 = 350;
+
+/// Volatility of an unrated player, in millionths
+pub const DEFAULT_VOLATILITY: u32 = 60_000;
+
+/// Score of a win, draw and a loss
+pub const WIN: Fixed = ONE;
+pub const DRAW: Fixed = ONE / 2;
+pub const LOSS: Fixed = 0;
+
+pub fn from_int(value: u32) -> Fixed {
+    value as Fixed * ONE
+}
+
+pub fn to_int(value: Fixed) -> u32 {
+    if value <= 0 {
+        return 0;
+    }
+
+    ((value + ONE / 2) / ONE) as u32
+}
+
+pub fn mul(a: Fixed, b: Fixed) -> Fixed {
+    a * b / ONE
+}
+
+pub fn div(a: Fixed, b: Fixed) -> Fixed {
+    a * ONE / b
+}
+
+/// Returns e^x
+pub fn exp(x: Fixed) -> Fixed {
+    // Keep result in range, ratings never need more than this
+    let x = x.clamp(-40 * ONE, 40 * ONE);
+
+    // e^x = 2^k * e^r where |r| <= ln(2) / 2
+    let k = if x >= 0 {
+        (x + LN2 / 2) / LN2
+    } else {
+        (x - LN2 / 2) / LN2
+    };
+
+    let r = x - k * LN2;
+
+    let mut term = ONE;
+    let mut sum = ONE;
+
+    for n in 1..20 {
+        term = mul(term, r) / n;
+        sum += term;
+    }
+
+    if k >= 0 {
+        sum << k
+    } else {
+        sum >> -k
+    }
+}
+
+/// Returns natural logarithm of x, x must be positive
+pub fn ln(x: Fixed) -> Fixed {
+    // ln(x) = k * ln(2) + ln(m) where 1 <= m < 2
+    let mut m = x;
+    let mut k = 0;
+
+    while m >= 2 * ONE {
+        m /= 2;
+        k += 1;
+    }
+
+    while m < ONE {
+        m *= 2;
+        k -= 1;
+    }
+
+    // ln(m) = 2 * atanh(z) where z = (m - 1) / (m + 1) < 1 / 3
+    let z = div(m - ONE, m + ONE);
+    let z_squared = mul(z, z);
+
+    let mut term = z;
+    let mut sum = 0;
+
+    for n in 0..20 {
+        sum += term / (2 * n + 1);
+        term = mul(term, z_squared);
+    }
+
+    k * LN2 + 2 * sum
+}
+
+/// Returns 10^x
+pub fn pow10(x: Fixed) -> Fixed {
+    exp(mul(x, LN10))
+}
+
+/// Returns square root of x
+pub fn sqrt(x: Fixed) -> Fixed {
+    if x <= 0 {
+        return 0;
+    }
+
+    let n = (x * ONE) as u128;
+    let mut root = n;
+    let mut next = (root + 1) / 2;
+
+    while next < root {
+        root = next;
+        next = (root + n / root) / 2;
+    }
+
+    root as Fixed
+}
+
+/// Returns expected score of a player rated `rating` against an opponent rated `opponent`
+pub fn elo_expected_score(rating: u32, opponent: u32) -> Fixed {
+    let exponent = (from_int(opponent) - from_int(rating)) / 400;
+
+    div(ONE, ONE + pow10(exponent))
+}
+
+/// Returns new Elo rating of a player rated `rating` who scored `score`
+/// against an opponent rated `opponent`
+pub fn elo(rating: u32, opponent: u32, score: Fixed, k_factor: u32) -> u32 {
+    let expected = elo_expected_score(rating, opponent);
+
+    to_int(from_int(rating) + k_factor as Fixed * (score - expected))
+}
+
+fn glicko2_g(phi: Fixed) -> Fixed {
+    div(ONE, sqrt(ONE + div(3 * mul(phi, phi), PI_SQUARED)))
+}
+
+/// Returns new volatility σ' of a player with deviation `phi` and volatility `sigma`
+/// given the estimated variance `v` and improvement `delta`, all on the Glicko-2 scale
+fn glicko2_volatility(phi: Fixed, sigma: Fixed, v: Fixed, delta: Fixed) -> Fixed {
+    let phi_squared = mul(phi, phi);
+    let delta_squared = mul(delta, delta);
+    let tau_squared = mul(TAU, TAU);
+    let a = ln(mul(sigma, sigma));
+
+    let f = |x: Fixed| {
+        let e_x = exp(x);
+        let d = phi_squared + v + e_x;
+
+        // e^x * (Δ² - φ² - v - e^x) / (2 * (φ² + v + e^x)²), computed as ratios to stay in range
+        mul(div(e_x, d), div(delta_squared - d, d)) / 2 - div(x - a, tau_squared)
+    };
+
+    let mut x_a = a;
+    let mut x_b = if delta_squared > phi_squared + v {
+        ln(delta_squared - phi_squared - v)
+    } else {
+        let mut k = 1;
+
+        while f(a - k * TAU) < 0 && k < MAX_ITERATIONS as Fixed {
+            k += 1;
+        }
+
+        a - k * TAU
+    };
+
+    let mut f_a = f(x_a);
+    let mut f_b = f(x_b);
+
+    // Illinois variant of the regula falsi
+    for _ in 0..MAX_ITERATIONS {
+        if (x_b - x_a).abs() <= EPSILON || f_b == f_a {
+            break;
+        }
+
+        let x_c = x_a + div(mul(x_a - x_b, f_a), f_b - f_a);
+        let f_c = f(x_c);
+
+        if f_c.signum() * f_b.signum() <= 0 {
+            x_a = x_b;
+            f_a = f_b;
+        } else {
+            f_a /= 2;
+        }
+
+        x_b = x_c;
+        f_b = f_c;
+    }
+
+    exp(x_a / 2)
+}
+
+/// Returns new Glicko-2 rating, rating deviation and volatility (in millionths) of a player
+/// rated `rating` with `deviation` and `volatility` after a rating period with `results`,
+/// each given as the opponent rating, opponent deviation and the score against them
+pub fn glicko2(
+    rating: u32,
+    deviation: u32,
+    volatility: u32,
+    results: &[(u32, u32, Fixed)],
+) -> (u32, u32, u32) {
+    // Convert to the Glicko-2 scale
+    let mu = div(from_int(rating) - from_int(CENTER_RATING), SCALE);
+    let phi = div(from_int(deviation), SCALE);
+    let sigma = volatility as Fixed * ONE / 1_000_000;
+
+    // 1 / v and Δ / v
+    let mut v_inv = 0;
+    let mut improvement = 0;
+
+    for &(opponent, opponent_deviation, score) in results {
+        let mu_j = div(from_int(opponent) - from_int(CENTER_RATING), SCALE);
+        let g = glicko2_g(div(from_int(opponent_deviation), SCALE));
+        let expected = div(ONE, ONE + exp(-mul(g, mu - mu_j)));
+
+        v_inv += mul(mul(g, g), mul(expected, ONE - expected));
+        improvement += mul(g, score - expected);
+    }
+
+    let v = div(ONE, v_inv.max(1));
+    let sigma_new = if results.is_empty() {
+        sigma
+    } else {
+        glicko2_volatility(phi, sigma, v, mul(v, improvement))
+    };
+
+    let phi_star = sqrt(mul(phi, phi) + mul(sigma_new, sigma_new));
+    let phi_new = div(ONE, sqrt(div(ONE, mul(phi_star, phi_star)) + v_inv));
+    let mu_new = mu + mul(mul(phi_new, phi_new), improvement);
+
+    // Convert back to the Glicko scale
+    let rating_new = from_int(CENTER_RATING) + mul(mu_new, SCALE);
+    let deviation_new = to_int(mul(phi_new, SCALE)).clamp(MIN_DEVIATION, MAX_DEVIATION);
+    let volatility_new = ((sigma_new * 1_000_000 + ONE / 2) / ONE) as u32;
+
+    (to_int(rating_new), deviation_new, volatility_new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exp_values() {
+        assert!((exp(ONE) - 2_718_281_828_459).abs() < 1_000);
+        assert!((exp(-ONE) - 367_879_441_171).abs() < 1_000);
+        assert_eq!(exp(0), ONE);
+    }
+
+    #[test]
+    fn sqrt_values() {
+        assert_eq!(sqrt(from_int(4)), from_int(2));
+        assert!((sqrt(from_int(2)) - 1_414_213_562_373).abs() < 10);
+    }
+
+    #[test]
+    fn elo_equal_ratings() {
+        assert_eq!(elo(1500, 1500, WIN, 32), 1516);
+        assert_eq!(elo(1500, 1500, DRAW, 32), 1500);
+        assert_eq!(elo(1500, 1500, LOSS, 32), 1484);
+    }
+
+    #[test]
+    fn elo_favourite_wins() {
+        assert_eq!(elo(1800, 1400, WIN, 32), 1803);
+        assert_eq!(elo(1400, 1800, LOSS, 32), 1397);
+    }
+
+    #[test]
+    fn ln_values() {
+        assert_eq!(ln(ONE), 0);
+        assert!((ln(from_int(2)) - LN2).abs() < 10);
+        assert!((ln(from_int(10)) - LN10).abs() < 100);
+        assert!((ln(ONE / 2) + LN2).abs() < 10);
+    }
+
+    #[test]
+    fn glicko2_unrated_players() {
+        assert_eq!(
+            glicko2(1500, 350, DEFAULT_VOLATILITY, &[(1500, 350, WIN)]),
+            (1662, 290, 60_000)
+        );
+        assert_eq!(
+            glicko2(1500, 350, DEFAULT_VOLATILITY, &[(1500, 350, LOSS)]),
+            (1338, 290, 60_000)
+        );
+    }
+
+    #[test]
+    fn glicko2_paper_example() {
+        // Example from Glickman's "Example of the Glicko-2 system"
+        let results = [(1400, 30, WIN), (1550, 100, LOSS), (1700, 300, LOSS)];
+
+        assert_eq!(
+            glicko2(1500, 200, DEFAULT_VOLATILITY, &results),
+            (1464, 152, 59_996)
+        );
+    }
+
+    #[test]
+    fn glicko2_upset_raises_volatility() {
+        let (rating, deviation, volatility) =
+            glicko2(3000, 30, DEFAULT_VOLATILITY, &[(100, 350, LOSS)]);
+
+        assert_eq!((rating, deviation), (2996, 32));
+        assert!(volatility > DEFAULT_VOLATILITY);
+    }
+
+    #[test]
+    fn glicko2_idle_period_raises_deviation() {
+        assert_eq!(
+            glicko2(1700, 100, DEFAULT_VOLATILITY, &[]),
+            (1700, 101, 60_000)
+        );
+    }
+}
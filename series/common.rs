@@ -0,0 +1,50 @@
+use scale::{Decode, Encode};
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Encode, Decode, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+    /// Caller is not allowed to perform the action
+    InvalidCaller,
+    /// Transferred balance does not match the stake
+    InvalidDeposit,
+    /// Series is waiting for players to deposit their stakes
+    SeriesNotFunded,
+    /// Series is no longer waiting for deposits
+    SeriesAlreadyFunded,
+    /// Series has already ended
+    SeriesAlreadyOver,
+    /// Funding deadline has passed, the series can only be aborted
+    FundingClosed,
+    /// Funding deadline has not passed yet
+    FundingOpen,
+    /// Series has not ended yet
+    SeriesNotOver,
+    /// Previous game of the series has not been recorded yet
+    GameInProgress,
+    /// There is no game of the series to record
+    GameNotCreated,
+    /// Game has not ended yet
+    GameNotOver,
+    /// Rematch has already been proposed
+    RematchAlreadyProposed,
+    /// There is no pending rematch proposal
+    NoRematchProposal,
+    /// Game contract could not be instantiated
+    InstantiationFailed,
+    /// Transfer of funds failed
+    TransferFailed,
+    Other,
+}
+
+impl core::convert::From<ink_env::Error> for Error {
+    fn from(error: ink_env::Error) -> Self {
+        match error {
+            ink_env::Error::TransferFailed | ink_env::Error::BelowSubsistenceThreshold => {
+                Self::TransferFailed
+            }
+            _ => Self::Other,
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Calls to the DotChess contracts playing games of the series
+
+use crate::common::Result;
+use dotchess::{ClockUnit, Status, TimeControl};
+use ink_env::{AccountId, Hash};
+
+type Balance = <ink_env::DefaultEnvironment as ink_env::Environment>::Balance;
+
+#[cfg(not(test))]
+mod contract {
+    use super::Balance;
+    use crate::common::{Error, Result};
+    use dotchess::{ClockUnit, DotChess, Game, Status, TimeControl};
+    use ink_env::{AccountId, Hash};
+
+    pub fn instantiate(
+        code_hash: Hash,
+        white: AccountId,
+        black: AccountId,
+        time_control: TimeControl,
+        clock_unit: ClockUnit,
+        endowment: Balance,
+        salt: [u8; 4],
+    ) -> Result<AccountId> {
+        let game = DotChess::with_time_control(
+            white,
+            black,
+            time_control,
+            clock_unit,
+            Game::FEN_NEW_GAME.into(),
+        )
+        .endowment(endowment)
+        .code_hash(code_hash)
+        .salt_bytes(salt)
+        .instantiate()
+        .map_err(|_| Error::InstantiationFailed)?;
+
+        Ok(ink_lang::ToAccountId::to_account_id(&game))
+    }
+
+    pub fn status(game: AccountId) -> Status {
+        let contract: DotChess = ink_env::call::FromAccountId::from_account_id(game);
+
+        contract.status()
+    }
+}
+
+/// Mock DotChess contracts, the off-chain environment cannot dispatch cross-contract calls
+#[cfg(test)]
+pub mod mock {
+    use super::Balance;
+    use dotchess::{ClockUnit, Status, TimeControl};
+    use ink_env::{AccountId, Hash};
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    /// Game contract as instantiated by the series
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Game {
+        pub code_hash: Hash,
+        pub white: AccountId,
+        pub black: AccountId,
+        pub time_control: TimeControl,
+        pub clock_unit: ClockUnit,
+        pub endowment: Balance,
+        pub status: Status,
+    }
+
+    thread_local! {
+        static GAMES: RefCell<HashMap<AccountId, Game>> = RefCell::new(HashMap::new());
+    }
+
+    /// Creates `game` at an account derived from `salt`, none if it is taken
+    pub fn instantiate(game: Game, salt: [u8; 4]) -> Option<AccountId> {
+        let mut account = [0; 32];
+        account[..4].copy_from_slice(&salt);
+        let account = AccountId::from(account);
+
+        GAMES.with(|games| {
+            let mut games = games.borrow_mut();
+
+            if games.contains_key(&account) {
+                return None;
+            }
+
+            games.insert(account, game);
+
+            Some(account)
+        })
+    }
+
+    /// Returns the `game` contract, panics if there is none
+    pub fn game(game: AccountId) -> Game {
+        GAMES.with(|games| {
+            games
+                .borrow()
+                .get(&game)
+                .cloned()
+                .expect("no game contract")
+        })
+    }
+
+    /// Sets status of the `game` contract
+    pub fn set_status(game: AccountId, status: Status) {
+        GAMES.with(|games| {
+            if let Some(game) = games.borrow_mut().get_mut(&game) {
+                game.status = status;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod contract {
+    use super::{mock, Balance};
+    use crate::common::{Error, Result};
+    use dotchess::{ClockUnit, Status, TimeControl};
+    use ink_env::{AccountId, Hash};
+
+    pub fn instantiate(
+        code_hash: Hash,
+        white: AccountId,
+        black: AccountId,
+        time_control: TimeControl,
+        clock_unit: ClockUnit,
+        endowment: Balance,
+        salt: [u8; 4],
+    ) -> Result<AccountId> {
+        let game = mock::Game {
+            code_hash,
+            white,
+            black,
+            time_control,
+            clock_unit,
+            endowment,
+            status: Status::Active,
+        };
+
+        mock::instantiate(game, salt).ok_or(Error::InstantiationFailed)
+    }
+
+    pub fn status(game: AccountId) -> Status {
+        mock::game(game).status
+    }
+}
+
+/// Instantiates a DotChess game of `white` and `black` from the contract `code_hash`
+/// with `endowment`, returns account of the game contract
+pub fn instantiate(
+    code_hash: Hash,
+    white: AccountId,
+    black: AccountId,
+    time_control: TimeControl,
+    clock_unit: ClockUnit,
+    endowment: Balance,
+    salt: [u8; 4],
+) -> Result<AccountId> {
+    contract::instantiate(
+        code_hash,
+        white,
+        black,
+        time_control,
+        clock_unit,
+        endowment,
+        salt,
+    )
+}
+
+/// Returns status of the DotChess `game`
+pub fn status(game: AccountId) -> Status {
+    contract::status(game)
+}
@@ -0,0 +1,881 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod common;
+mod game;
+
+pub use crate::common::{Error, Result};
+pub use crate::series::{Outcome, Phase, Series, SeriesGame};
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod series {
+
+    use crate::common::{Error, Result};
+    use crate::game;
+    use dotchess::{ClockUnit, GameOverReason, Side, Status, TimeControl};
+    use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
+    use ink_storage::{Pack, Vec};
+    use scale::{Decode, Encode};
+
+    /// Event emitted when the `number`th game (zero based, counted over all
+    /// matches) of the series is created in the `game` contract
+    #[ink(event)]
+    pub struct GameCreated {
+        #[ink(topic)]
+        game: AccountId,
+        number: u32,
+        white: AccountId,
+        black: AccountId,
+    }
+
+    /// Event emitted when result of the `number`th game is recorded
+    #[ink(event)]
+    pub struct ResultRecorded {
+        number: u32,
+        outcome: Outcome,
+    }
+
+    /// Event emitted when a match of the series is decided, `winner` is none
+    /// when it is drawn. Points are counted in half points.
+    #[ink(event)]
+    pub struct MatchOver {
+        winner: Option<AccountId>,
+        first_points: u32,
+        second_points: u32,
+    }
+
+    /// Event emitted when the series is aborted after the funding deadline,
+    /// the deposits are returned
+    #[ink(event)]
+    pub struct SeriesAborted {}
+
+    /// Event emitted when the `player` proposes a rematch
+    #[ink(event)]
+    pub struct RematchProposed {
+        #[ink(topic)]
+        player: AccountId,
+    }
+
+    /// Event emitted when the rematch proposed by the `player` is accepted
+    #[ink(event)]
+    pub struct RematchAccepted {
+        #[ink(topic)]
+        player: AccountId,
+    }
+
+    /// Event emitted when the rematch proposed by the `player` is cancelled
+    #[ink(event)]
+    pub struct RematchCancelled {
+        #[ink(topic)]
+        player: AccountId,
+    }
+
+    /// Result of a game of the series
+    #[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(feature = "std", derive(Debug, scale_info::TypeInfo, StorageLayout))]
+    pub enum Outcome {
+        WhiteWins,
+        BlackWins,
+        Draw,
+    }
+
+    /// Phase of the current match, `winner` is none when the match is drawn
+    #[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(feature = "std", derive(Debug, scale_info::TypeInfo, StorageLayout))]
+    pub enum Phase {
+        Funding,
+        Running,
+        Finished { winner: Option<AccountId> },
+    }
+
+    /// Game of the series played in the DotChess contract `game`
+    #[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    pub struct SeriesGame {
+        pub white: AccountId,
+        pub black: AccountId,
+        pub game: AccountId,
+        pub outcome: Option<Outcome>,
+    }
+
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    struct Config {
+        games: u32,
+        stake: Balance,
+        code_hash: Hash,
+        time_control: TimeControl,
+        clock_unit: ClockUnit,
+    }
+
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    struct State {
+        phase: Phase,
+        /// Last block of the first match deposits
+        funding_deadline: BlockNumber,
+        first_deposited: bool,
+        second_deposited: bool,
+        /// Index of the first game of the current match
+        match_start: u32,
+        rematch_proposer: Option<AccountId>,
+    }
+
+    #[ink(storage)]
+    pub struct Series {
+        /// Player with white in the first game
+        first: AccountId,
+        /// Player with black in the first game
+        second: AccountId,
+        /// Series settings
+        config: Pack<Config>,
+        /// Deposits, phase and rematch proposal
+        state: Pack<State>,
+        /// Games of all matches
+        games: Vec<SeriesGame>,
+    }
+
+    impl Series {
+        /// Opens a best of `games` match of `first` and `second`, games are instantiated
+        /// from the DotChess contract `code_hash` with alternating colors, `first` has
+        /// white in the first game
+        ///
+        /// Both players deposit `stake` in the next `funding_window` blocks, otherwise
+        /// the series can be aborted. The match winner is paid both stakes once
+        /// the match is decided. A drawn match returns the stakes.
+        #[ink(constructor)]
+        #[allow(clippy::too_many_arguments)]
+        pub fn new(
+            first: AccountId,
+            second: AccountId,
+            games: u32,
+            time_control: TimeControl,
+            clock_unit: ClockUnit,
+            stake: Balance,
+            funding_window: BlockNumber,
+            code_hash: Hash,
+        ) -> Self {
+            assert!(games > 0, "match must have at least one game");
+
+            Self {
+                first,
+                second,
+                config: Pack::new(Config {
+                    games,
+                    stake,
+                    code_hash,
+                    time_control,
+                    clock_unit,
+                }),
+                state: Pack::new(State {
+                    phase: if stake == 0 {
+                        Phase::Running
+                    } else {
+                        Phase::Funding
+                    },
+                    funding_deadline: Self::env().block_number() + funding_window,
+                    first_deposited: stake == 0,
+                    second_deposited: stake == 0,
+                    match_start: 0,
+                    rematch_proposer: None,
+                }),
+                games: Vec::new(),
+            }
+        }
+
+        /// Deposits stake of the caller, the match starts once both players deposit
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) -> Result<()> {
+            match self.state.phase {
+                Phase::Funding => {}
+                Phase::Running => return Err(Error::SeriesAlreadyFunded),
+                Phase::Finished { .. } => return Err(Error::SeriesAlreadyOver),
+            }
+
+            if self.env().block_number() > self.state.funding_deadline {
+                return Err(Error::FundingClosed);
+            }
+
+            if self.env().transferred_balance() != self.config.stake {
+                return Err(Error::InvalidDeposit);
+            }
+
+            let caller = self.env().caller();
+
+            if caller == self.first && !self.state.first_deposited {
+                self.state.first_deposited = true;
+            } else if caller == self.second && !self.state.second_deposited {
+                self.state.second_deposited = true;
+            } else {
+                return Err(Error::InvalidCaller);
+            }
+
+            if self.state.first_deposited && self.state.second_deposited {
+                self.state.phase = Phase::Running;
+            }
+
+            Ok(())
+        }
+
+        /// Aborts the series after the funding deadline has passed without both
+        /// deposits, returning the deposit
+        #[ink(message)]
+        pub fn abort(&mut self) -> Result<()> {
+            match self.state.phase {
+                Phase::Funding => {}
+                Phase::Running => return Err(Error::SeriesAlreadyFunded),
+                Phase::Finished { .. } => return Err(Error::SeriesAlreadyOver),
+            }
+
+            if self.env().block_number() <= self.state.funding_deadline {
+                return Err(Error::FundingOpen);
+            }
+
+            self.state.phase = Phase::Finished { winner: None };
+
+            let stake = self.config.stake;
+
+            if self.state.first_deposited {
+                self.pay(self.first, stake)?;
+            }
+
+            if self.state.second_deposited {
+                self.pay(self.second, stake)?;
+            }
+
+            self.env().emit_event(SeriesAborted {});
+
+            Ok(())
+        }
+
+        /// Instantiates the next game of the match with colors swapped, transferred
+        /// balance is passed on as the game contract endowment
+        #[ink(message, payable)]
+        pub fn start_game(&mut self, salt: [u8; 4]) -> Result<AccountId> {
+            self.ensure_running()?;
+
+            if self.current_game().is_some() {
+                return Err(Error::GameInProgress);
+            }
+
+            let game = self.instantiate_game(self.env().transferred_balance(), salt)?;
+
+            self.add_game(game);
+
+            Ok(game)
+        }
+
+        /// Records result of the finished current game, paying out the stakes
+        /// if it decides the match
+        ///
+        /// Aborted or voided game is not scored, it is removed from the series
+        /// so the game can be started again with the same colors
+        #[ink(message)]
+        pub fn report_result(&mut self) -> Result<()> {
+            self.ensure_running()?;
+
+            let game = self.current_game().ok_or(Error::GameNotCreated)?;
+
+            match Self::outcome(game::status(game.game))? {
+                Some(outcome) => self.record_result(outcome),
+                None => {
+                    self.games.pop();
+
+                    Ok(())
+                }
+            }
+        }
+
+        /// Proposes another match with the same settings once the current one is over,
+        /// transferred balance must equal the stake
+        #[ink(message, payable)]
+        pub fn propose_rematch(&mut self) -> Result<()> {
+            self.ensure_finished()?;
+
+            let caller = self.env().caller();
+
+            if caller != self.first && caller != self.second {
+                return Err(Error::InvalidCaller);
+            }
+
+            if self.state.rematch_proposer.is_some() {
+                return Err(Error::RematchAlreadyProposed);
+            }
+
+            if self.env().transferred_balance() != self.config.stake {
+                return Err(Error::InvalidDeposit);
+            }
+
+            self.state.rematch_proposer = Some(caller);
+            self.env().emit_event(RematchProposed { player: caller });
+
+            Ok(())
+        }
+
+        /// Accepts rematch proposed by the opponent and instantiates the first game
+        /// of the new match, transferred balance must cover the stake and the rest
+        /// is passed on as the game contract endowment. Colors keep alternating
+        /// from the last game.
+        #[ink(message, payable)]
+        pub fn accept_rematch(&mut self, salt: [u8; 4]) -> Result<AccountId> {
+            self.ensure_finished()?;
+
+            let proposer = self
+                .state
+                .rematch_proposer
+                .ok_or(Error::NoRematchProposal)?;
+
+            if self.env().caller() != self.opponent(proposer) {
+                return Err(Error::InvalidCaller);
+            }
+
+            let endowment = self
+                .env()
+                .transferred_balance()
+                .checked_sub(self.config.stake)
+                .ok_or(Error::InvalidDeposit)?;
+
+            let game = self.instantiate_game(endowment, salt)?;
+
+            self.state.phase = Phase::Running;
+            self.state.match_start = self.games.len();
+            self.state.rematch_proposer = None;
+
+            self.env().emit_event(RematchAccepted { player: proposer });
+
+            self.add_game(game);
+
+            Ok(game)
+        }
+
+        /// Cancels (or declines) the rematch proposal, refunding the proposer
+        #[ink(message)]
+        pub fn cancel_rematch(&mut self) -> Result<()> {
+            let proposer = self
+                .state
+                .rematch_proposer
+                .ok_or(Error::NoRematchProposal)?;
+
+            let caller = self.env().caller();
+
+            if caller != self.first && caller != self.second {
+                return Err(Error::InvalidCaller);
+            }
+
+            self.state.rematch_proposer = None;
+
+            if self.config.stake > 0 {
+                self.env().transfer(proposer, self.config.stake)?;
+            }
+
+            self.env().emit_event(RematchCancelled { player: proposer });
+
+            Ok(())
+        }
+
+        /// Player with white in the first game and their opponent
+        #[ink(message)]
+        pub fn players(&self) -> (AccountId, AccountId) {
+            (self.first, self.second)
+        }
+
+        /// Phase of the current match
+        #[ink(message)]
+        pub fn phase(&self) -> Phase {
+            self.state.phase
+        }
+
+        /// Number of games of a match
+        #[ink(message)]
+        pub fn games_per_match(&self) -> u32 {
+            self.config.games
+        }
+
+        /// Games of all matches of the series
+        #[ink(message)]
+        pub fn games(&self) -> alloc::vec::Vec<SeriesGame> {
+            self.games.iter().copied().collect()
+        }
+
+        /// Half points of the first and the second player in the current match
+        #[ink(message)]
+        pub fn score(&self) -> (u32, u32) {
+            self.games
+                .iter()
+                .skip(self.state.match_start as usize)
+                .fold((0, 0), |(first, second), game| {
+                    let winner = match game.outcome {
+                        Some(Outcome::WhiteWins) => Some(game.white),
+                        Some(Outcome::BlackWins) => Some(game.black),
+                        Some(Outcome::Draw) => None,
+                        None => return (first, second),
+                    };
+
+                    match winner {
+                        Some(winner) if winner == self.first => (first + 2, second),
+                        Some(_) => (first, second + 2),
+                        None => (first + 1, second + 1),
+                    }
+                })
+        }
+
+        /// Player who proposed a rematch, if any
+        #[ink(message)]
+        pub fn rematch_proposer(&self) -> Option<AccountId> {
+            self.state.rematch_proposer
+        }
+
+        /// Instantiates the next game of the series with `endowment`
+        fn instantiate_game(&self, endowment: Balance, salt: [u8; 4]) -> Result<AccountId> {
+            let (white, black) = self.next_colors();
+
+            game::instantiate(
+                self.config.code_hash,
+                white,
+                black,
+                self.config.time_control,
+                self.config.clock_unit,
+                endowment,
+                salt,
+            )
+        }
+
+        /// Returns outcome of a game with `status`, none if the game does not count
+        fn outcome(status: Status) -> Result<Option<Outcome>> {
+            match status {
+                Status::Finished {
+                    reason: GameOverReason::Aborted,
+                    ..
+                }
+                | Status::Finished {
+                    reason: GameOverReason::Void,
+                    ..
+                } => Ok(None),
+                Status::Finished {
+                    winner: Some(Side::White),
+                    ..
+                } => Ok(Some(Outcome::WhiteWins)),
+                Status::Finished {
+                    winner: Some(Side::Black),
+                    ..
+                } => Ok(Some(Outcome::BlackWins)),
+                Status::Finished { winner: None, .. } => Ok(Some(Outcome::Draw)),
+                _ => Err(Error::GameNotOver),
+            }
+        }
+
+        fn add_game(&mut self, game: AccountId) {
+            let (white, black) = self.next_colors();
+            let number = self.games.len();
+
+            self.games.push(SeriesGame {
+                white,
+                black,
+                game,
+                outcome: None,
+            });
+
+            self.env().emit_event(GameCreated {
+                game,
+                number,
+                white,
+                black,
+            });
+        }
+
+        fn record_result(&mut self, outcome: Outcome) -> Result<()> {
+            if self.current_game().is_none() {
+                return Err(Error::GameNotCreated);
+            }
+
+            let number = self.games.len() - 1;
+
+            if let Some(game) = self.games.get_mut(number) {
+                game.outcome = Some(outcome);
+            }
+
+            self.env().emit_event(ResultRecorded { number, outcome });
+
+            let (first_points, second_points) = self.score();
+            let games = self.config.games;
+            let played = self.games.len() - self.state.match_start;
+
+            // Match is decided by more than half of the points or after its last game
+            if first_points > games || second_points > games || played >= games {
+                return self.finish_match(first_points, second_points);
+            }
+
+            Ok(())
+        }
+
+        fn finish_match(&mut self, first_points: u32, second_points: u32) -> Result<()> {
+            let stake = self.config.stake;
+
+            let winner = if first_points > second_points {
+                Some(self.first)
+            } else if second_points > first_points {
+                Some(self.second)
+            } else {
+                None
+            };
+
+            self.state.phase = Phase::Finished { winner };
+
+            match winner {
+                Some(winner) => self.pay(winner, 2 * stake)?,
+                None => {
+                    self.pay(self.first, stake)?;
+                    self.pay(self.second, stake)?;
+                }
+            }
+
+            self.env().emit_event(MatchOver {
+                winner,
+                first_points,
+                second_points,
+            });
+
+            Ok(())
+        }
+
+        fn pay(&mut self, account: AccountId, amount: Balance) -> Result<()> {
+            if amount > 0 {
+                self.env().transfer(account, amount)?;
+            }
+
+            Ok(())
+        }
+
+        /// Returns the game of the current match whose result is not recorded yet
+        fn current_game(&self) -> Option<SeriesGame> {
+            let number = self.games.len().checked_sub(1)?;
+
+            if number < self.state.match_start {
+                return None;
+            }
+
+            self.games
+                .get(number)
+                .copied()
+                .filter(|game| game.outcome.is_none())
+        }
+
+        /// Returns white and black of the next game, colors alternate every game
+        fn next_colors(&self) -> (AccountId, AccountId) {
+            if self.games.len() % 2 == 0 {
+                (self.first, self.second)
+            } else {
+                (self.second, self.first)
+            }
+        }
+
+        fn opponent(&self, player: AccountId) -> AccountId {
+            if player == self.first {
+                self.second
+            } else {
+                self.first
+            }
+        }
+
+        fn ensure_running(&self) -> Result<()> {
+            match self.state.phase {
+                Phase::Funding => Err(Error::SeriesNotFunded),
+                Phase::Running => Ok(()),
+                Phase::Finished { .. } => Err(Error::SeriesAlreadyOver),
+            }
+        }
+
+        fn ensure_finished(&self) -> Result<()> {
+            match self.state.phase {
+                Phase::Finished { .. } => Ok(()),
+                _ => Err(Error::SeriesNotOver),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_lang as ink;
+
+        fn accounts() -> ink_env::test::DefaultAccounts<ink_env::DefaultEnvironment> {
+            ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().unwrap()
+        }
+
+        fn set_caller(caller: AccountId, transferred: Balance) {
+            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
+                caller,
+                contract(),
+                1000000,
+                transferred,
+                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])),
+            );
+        }
+
+        fn contract() -> AccountId {
+            ink_env::account_id::<ink_env::DefaultEnvironment>().unwrap()
+        }
+
+        fn balance(account: AccountId) -> Balance {
+            ink_env::test::get_account_balance::<ink_env::DefaultEnvironment>(account).unwrap()
+        }
+
+        /// Off-chain environment does not move transferred value
+        fn set_contract_balance(amount: Balance) {
+            ink_env::test::set_account_balance::<ink_env::DefaultEnvironment>(contract(), amount)
+                .unwrap();
+        }
+
+        fn series(games: u32, stake: Balance) -> Series {
+            let accounts = accounts();
+
+            Series::new(
+                accounts.alice,
+                accounts.bob,
+                games,
+                TimeControl::Fischer {
+                    base: 100,
+                    increment: 1,
+                },
+                ClockUnit::Blocks,
+                stake,
+                5,
+                Hash::from([0x42; 32]),
+            )
+        }
+
+        #[ink::test]
+        fn match_waits_for_deposits() {
+            let accounts = accounts();
+            let mut series = series(3, 10);
+
+            assert_eq!(series.phase(), Phase::Funding);
+            assert_eq!(series.start_game([0; 4]), Err(Error::SeriesNotFunded));
+
+            set_caller(accounts.alice, 5);
+            assert_eq!(series.deposit(), Err(Error::InvalidDeposit));
+
+            set_caller(accounts.charlie, 10);
+            assert_eq!(series.deposit(), Err(Error::InvalidCaller));
+
+            for player in &[accounts.alice, accounts.bob] {
+                set_caller(*player, 10);
+                series.deposit().unwrap();
+            }
+
+            assert_eq!(series.phase(), Phase::Running);
+            assert_eq!(series.deposit(), Err(Error::SeriesAlreadyFunded));
+        }
+
+        #[ink::test]
+        fn unfunded_series_is_aborted_after_deadline() {
+            let accounts = accounts();
+            let mut series = series(3, 10);
+
+            set_caller(accounts.alice, 10);
+            series.deposit().unwrap();
+
+            set_contract_balance(10);
+            let alice_balance = balance(accounts.alice);
+            let bob_balance = balance(accounts.bob);
+
+            assert_eq!(series.abort(), Err(Error::FundingOpen));
+
+            for _ in 0..6 {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>().unwrap();
+            }
+
+            set_caller(accounts.bob, 10);
+            assert_eq!(series.deposit(), Err(Error::FundingClosed));
+
+            set_caller(accounts.charlie, 0);
+            series.abort().unwrap();
+
+            assert_eq!(series.phase(), Phase::Finished { winner: None });
+            assert_eq!(balance(accounts.alice), alice_balance + 10);
+            assert_eq!(balance(accounts.bob), bob_balance);
+            assert_eq!(series.abort(), Err(Error::SeriesAlreadyOver));
+        }
+
+        #[ink::test]
+        fn best_of_three_ends_when_decided() {
+            let accounts = accounts();
+            let mut series = series(3, 10);
+
+            for player in &[accounts.alice, accounts.bob] {
+                set_caller(*player, 10);
+                series.deposit().unwrap();
+            }
+
+            set_contract_balance(20);
+            let alice_balance = balance(accounts.alice);
+
+            series.add_game(AccountId::from([0x10; 32]));
+            assert_eq!(series.start_game([0; 4]), Err(Error::GameInProgress));
+            series.record_result(Outcome::WhiteWins).unwrap();
+
+            series.add_game(AccountId::from([0x11; 32]));
+            series.record_result(Outcome::BlackWins).unwrap();
+
+            let games = series.games();
+
+            // Colors are swapped in the second game
+            assert_eq!(
+                (games[1].white, games[1].black),
+                (accounts.bob, accounts.alice)
+            );
+            assert_eq!(series.score(), (4, 0));
+            assert_eq!(
+                series.phase(),
+                Phase::Finished {
+                    winner: Some(accounts.alice)
+                }
+            );
+            assert_eq!(balance(accounts.alice), alice_balance + 20);
+            assert_eq!(series.start_game([0; 4]), Err(Error::SeriesAlreadyOver));
+        }
+
+        #[ink::test]
+        fn drawn_match_and_rematch() {
+            let accounts = accounts();
+            let mut series = series(1, 10);
+
+            for player in &[accounts.alice, accounts.bob] {
+                set_caller(*player, 10);
+                series.deposit().unwrap();
+            }
+
+            set_contract_balance(20);
+            let bob_balance = balance(accounts.bob);
+
+            assert_eq!(series.propose_rematch(), Err(Error::SeriesNotOver));
+
+            series.add_game(AccountId::from([0x10; 32]));
+            series.record_result(Outcome::Draw).unwrap();
+
+            assert_eq!(series.phase(), Phase::Finished { winner: None });
+            assert_eq!(balance(accounts.bob), bob_balance + 10);
+
+            set_caller(accounts.bob, 10);
+            series.propose_rematch().unwrap();
+
+            assert_eq!(series.accept_rematch([1; 4]), Err(Error::InvalidCaller));
+
+            set_caller(accounts.alice, 5);
+            assert_eq!(series.accept_rematch([1; 4]), Err(Error::InvalidDeposit));
+
+            set_caller(accounts.alice, 12);
+            let game = series.accept_rematch([1; 4]).unwrap();
+
+            assert_eq!(series.phase(), Phase::Running);
+            assert_eq!(series.score(), (0, 0));
+            assert_eq!(series.games()[1].game, game);
+            assert_eq!(series.games()[1].white, accounts.bob);
+            assert_eq!(game::mock::game(game).white, accounts.bob);
+            assert_eq!(game::mock::game(game).endowment, 2);
+            assert_eq!(series.start_game([2; 4]), Err(Error::GameInProgress));
+        }
+
+        #[ink::test]
+        fn games_are_played_in_game_contracts() {
+            let accounts = accounts();
+            let mut series = series(2, 0);
+
+            assert_eq!(series.report_result(), Err(Error::GameNotCreated));
+
+            set_caller(accounts.alice, 7);
+            let first = series.start_game([1; 4]).unwrap();
+
+            assert_eq!(
+                game::mock::game(first),
+                game::mock::Game {
+                    code_hash: Hash::from([0x42; 32]),
+                    white: accounts.alice,
+                    black: accounts.bob,
+                    time_control: TimeControl::Fischer {
+                        base: 100,
+                        increment: 1,
+                    },
+                    clock_unit: ClockUnit::Blocks,
+                    endowment: 7,
+                    status: Status::Active,
+                }
+            );
+            assert_eq!(series.report_result(), Err(Error::GameNotOver));
+
+            game::mock::set_status(
+                first,
+                Status::Finished {
+                    winner: None,
+                    reason: GameOverReason::Aborted,
+                },
+            );
+            series.report_result().unwrap();
+
+            // Aborted game is not scored and is started again with the same colors
+            assert!(series.games().is_empty());
+            assert_eq!(series.start_game([1; 4]), Err(Error::InstantiationFailed));
+
+            let second = series.start_game([2; 4]).unwrap();
+
+            assert_eq!(game::mock::game(second).white, accounts.alice);
+
+            game::mock::set_status(
+                second,
+                Status::Finished {
+                    winner: None,
+                    reason: GameOverReason::Void,
+                },
+            );
+            series.report_result().unwrap();
+
+            let third = series.start_game([3; 4]).unwrap();
+
+            game::mock::set_status(
+                third,
+                Status::Finished {
+                    winner: None,
+                    reason: GameOverReason::DrawAgreement,
+                },
+            );
+            series.report_result().unwrap();
+
+            assert_eq!(series.score(), (1, 1));
+            assert_eq!(series.games()[0].outcome, Some(Outcome::Draw));
+            assert_eq!(series.phase(), Phase::Running);
+
+            let fourth = series.start_game([4; 4]).unwrap();
+
+            assert_eq!(game::mock::game(fourth).white, accounts.bob);
+
+            game::mock::set_status(
+                fourth,
+                Status::Finished {
+                    winner: Some(Side::White),
+                    reason: GameOverReason::Checkmate,
+                },
+            );
+            series.report_result().unwrap();
+
+            assert_eq!(series.score(), (1, 3));
+            assert_eq!(
+                series.phase(),
+                Phase::Finished {
+                    winner: Some(accounts.bob)
+                }
+            );
+        }
+    }
+}
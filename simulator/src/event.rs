@@ -0,0 +1,112 @@
+//! Contract events as recorded by the off-chain environment
+
+use dotchess::{
+    ClockUnit, DrawOfferChange, GameOverReason, Handicap, Mov, MoveFlags, Piece, Side, ZobristHash,
+};
+use scale::Decode;
+
+/// Names of the contract events, in the order they are declared
+const EVENT_NAMES: [&str; 15] = [
+    "DrawOfferUpdate",
+    "BoardUpdate",
+    "TakebackRequest",
+    "TakebackAccept",
+    "TakebackDecline",
+    "DelegateUpdate",
+    "ChannelUpdate",
+    "ClockPause",
+    "TimeAdded",
+    "Adjudicated",
+    "CollectibleMinted",
+    "Committed",
+    "TimeoutReported",
+    "CheckpointSubmitted",
+    "GameOver",
+];
+
+/// Contract events the simulator prints with their fields, the other events
+/// are printed by name only
+///
+/// Variants are SCALE encoded with the indices of `dotchess::event_index`
+#[derive(Debug, Decode)]
+enum Event {
+    #[codec(index = 0)]
+    DrawOfferUpdate {
+        side: Side,
+        offer: bool,
+        change: DrawOfferChange,
+    },
+    #[codec(index = 1)]
+    BoardUpdate {
+        next_side: Side,
+        clock_unit: ClockUnit,
+        next_move_deadline: u64,
+        last_move: Mov,
+        last_side_time_left: u32,
+        fen: String,
+        ply: u32,
+        san: String,
+        flags: MoveFlags,
+        piece: Piece,
+        captured: Option<Piece>,
+        zobrist: ZobristHash,
+        white_time_left: u32,
+        black_time_left: u32,
+    },
+    #[codec(index = 3)]
+    TakebackAccept {
+        side: Side,
+        n_plies: u32,
+        fen: String,
+    },
+    #[codec(index = 14)]
+    GameOver {
+        winner: Option<Side>,
+        reason: GameOverReason,
+        handicap: Option<Handicap>,
+    },
+}
+
+/// Describes SCALE encoded event `data`
+pub fn describe(data: &[u8]) -> String {
+    if let Ok(event) = Event::decode(&mut &data[..]) {
+        return format!("{:?}", event);
+    }
+
+    match data
+        .first()
+        .and_then(|index| EVENT_NAMES.get(*index as usize))
+    {
+        Some(name) => name.to_string(),
+        None => "<undecodable>".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dotchess::event_index;
+
+    #[test]
+    fn names_match_contract_indices() {
+        assert_eq!(
+            EVENT_NAMES[event_index::DRAW_OFFER_UPDATE as usize],
+            "DrawOfferUpdate"
+        );
+        assert_eq!(
+            EVENT_NAMES[event_index::BOARD_UPDATE as usize],
+            "BoardUpdate"
+        );
+        assert_eq!(
+            EVENT_NAMES[event_index::TAKEBACK_ACCEPT as usize],
+            "TakebackAccept"
+        );
+        assert_eq!(
+            EVENT_NAMES[event_index::CHECKPOINT_SUBMITTED as usize],
+            "CheckpointSubmitted"
+        );
+        assert_eq!(EVENT_NAMES[event_index::GAME_OVER as usize], "GameOver");
+        assert_eq!(describe(&[event_index::CLOCK_PAUSE, 1]), "ClockPause");
+        assert_eq!(describe(&[0xff]), "<undecodable>");
+    }
+}
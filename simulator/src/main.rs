@@ -0,0 +1,73 @@
+//! Runs DotChess games in the ink! off-chain environment
+//!
+//! Usage: simulator SCRIPT
+//!        simulator --pgn PGN
+//!
+//! See `script` module for the script commands. Each command is echoed along with
+//! emitted events and failed calls, final game status and balances are printed
+//! at the end. With `--pgn` the PGN game is replayed as a game of alice and bob.
+
+extern crate dotchess;
+
+mod event;
+mod pgn;
+mod script;
+mod sim;
+
+use crate::script::Command;
+use crate::sim::Simulator;
+use ink_env::DefaultEnvironment;
+use std::{env, fs, process};
+
+pub fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let (source, commands) = match (args.get(1).map(String::as_str), args.get(2)) {
+        (Some("--pgn"), Some(path)) => (
+            format!("pgn {}", path),
+            vec![(1, Command::Pgn(path.clone()))],
+        ),
+        (Some(path), None) => {
+            let source = fs::read_to_string(path).unwrap_or_else(|error| {
+                eprintln!("{}: {}", path, error);
+                process::exit(1);
+            });
+
+            let commands = script::parse(&source).unwrap_or_else(|error| {
+                eprintln!("{}: {}", path, error);
+                process::exit(1);
+            });
+
+            (source, commands)
+        }
+        _ => {
+            eprintln!("usage: simulator SCRIPT | simulator --pgn PGN");
+            process::exit(1);
+        }
+    };
+
+    let lines: Vec<&str> = source.lines().collect();
+
+    ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        let mut simulator = Simulator::new(accounts);
+
+        for (line, command) in commands.iter() {
+            println!("> {}", lines[line - 1].trim());
+
+            for output in simulator.run(command) {
+                println!("  {}", output);
+            }
+        }
+
+        println!();
+
+        for command in [Command::Status, Command::Balances].iter() {
+            for output in simulator.run(command) {
+                println!("{}", output);
+            }
+        }
+
+        Ok(())
+    })
+    .expect("off-chain environment failed");
+}
@@ -0,0 +1,103 @@
+//! Reading moves of PGN files
+
+use dotchess::{Game, Mov};
+
+/// Moves of a PGN game in standard algebraic notation, starting from `fen`
+/// if the game has the FEN tag
+#[derive(Debug, PartialEq, Eq)]
+pub struct Pgn {
+    pub fen: Option<String>,
+    pub moves: Vec<String>,
+}
+
+/// Parses first game of `input`, comments, variations and annotations are skipped
+pub fn parse(input: &str) -> Pgn {
+    let mut fen = None;
+    let mut movetext = String::new();
+
+    for line in input.lines() {
+        let line = line.trim();
+
+        if line.starts_with('[') {
+            if let Some(value) = line.strip_prefix("[FEN \"") {
+                fen = value.split('"').next().map(String::from);
+            }
+        } else if !line.starts_with('%') {
+            movetext.push_str(line.split(';').next().unwrap_or_default());
+            movetext.push(' ');
+        }
+    }
+
+    // Strip comments and variations, which may be nested
+    let mut depth = 0;
+    let mut in_comment = false;
+    let mut stripped = String::new();
+
+    for char in movetext.chars() {
+        match char {
+            '{' => in_comment = true,
+            '}' => in_comment = false,
+            '(' if !in_comment => depth += 1,
+            ')' if !in_comment => depth -= 1,
+            _ if !in_comment && depth == 0 => stripped.push(char),
+            _ => {}
+        }
+    }
+
+    let moves = stripped
+        .split_whitespace()
+        .filter(|token| !["1-0", "0-1", "1/2-1/2", "*"].contains(token))
+        .map(|token| token.trim_start_matches(|char: char| char.is_ascii_digit() || char == '.'))
+        .filter(|token| !token.is_empty() && !token.starts_with('$'))
+        .map(String::from)
+        .collect();
+
+    Pgn { fen, moves }
+}
+
+/// Returns legal move of `game` written as `san`, check marks and annotations are ignored
+pub fn find_move(game: &Game, san: &str) -> Option<Mov> {
+    let san = normalize(san);
+
+    game.legal_moves()
+        .iter()
+        .copied()
+        .find(|mov| match game.san(mov) {
+            Ok(mov_san) => normalize(&mov_san) == san,
+            Err(_) => false,
+        })
+}
+
+fn normalize(san: &str) -> String {
+    san.trim_end_matches(|char| "+#!?".contains(char))
+        .replace('0', "O")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::TryInto;
+
+    #[test]
+    fn parse_pgn() {
+        let pgn = parse(
+            "[Event \"Casual\"]
+            [FEN \"4k3/8/8/8/8/8/4P3/4K3 w - - 0 1\"]
+
+            1. e4 {best by test} Kd7 (1... Ke7 2. e5) 2.e5 $1 Kc6 ; comment
+            3. Ke2 1/2-1/2",
+        );
+
+        assert_eq!(pgn.fen, Some("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1".into()));
+        assert_eq!(pgn.moves, ["e4", "Kd7", "e5", "Kc6", "Ke2"]);
+    }
+
+    #[test]
+    fn find_moves() {
+        let game = Game::new("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        assert_eq!(find_move(&game, "0-0"), "e1g1".try_into().ok());
+        assert_eq!(find_move(&game, "Rxa8+!"), "a1a8".try_into().ok());
+        assert_eq!(find_move(&game, "Rh9"), None);
+    }
+}
@@ -0,0 +1,253 @@
+//! Simulation scripts
+//!
+//! A script holds one command per line, `#` starts a comment. Accounts are
+//! the off-chain default accounts `alice`, `bob`, `charlie`, `django`, `eve`
+//! and `frank`. Commands are:
+//!
+//! ```text
+//! balance ACCOUNT AMOUNT                          sets balance of the account
+//! game WHITE BLACK STAKE BASE INCREMENT [FEN]     instantiates a new game
+//! deposit ACCOUNT AMOUNT                          deposits stake
+//! advance BLOCKS                                  advances block number
+//! move ACCOUNT MOVE                               makes a move, e.g. e2e4 or a7a8Q
+//! offer_draw ACCOUNT [true|false]                 offers (or withdraws) a draw
+//! decline_draw ACCOUNT                            declines draw offer of the opponent
+//! resign ACCOUNT                                  resigns the game
+//! report_abandonment ACCOUNT                      reports opponent out of time
+//! pgn PATH                                        plays moves of a PGN file,
+//!                                                 instantiating a game of alice
+//!                                                 and bob if there is none
+//! status                                          prints game status and FEN
+//! balances                                        prints balances
+//! ```
+
+use core::convert::TryInto;
+use dotchess::Mov;
+use std::fmt;
+use std::str::SplitWhitespace;
+
+type Balance = <ink_env::DefaultEnvironment as ink_env::Environment>::Balance;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Balance {
+        account: String,
+        amount: Balance,
+    },
+    Game {
+        white: String,
+        black: String,
+        stake: Balance,
+        base: u32,
+        increment: u32,
+        fen: Option<String>,
+    },
+    Deposit {
+        account: String,
+        amount: Balance,
+    },
+    Advance(u32),
+    Move {
+        account: String,
+        mov: Mov,
+    },
+    OfferDraw {
+        account: String,
+        offer: bool,
+    },
+    DeclineDraw(String),
+    Resign(String),
+    ReportAbandonment(String),
+    Pgn(String),
+    Status,
+    Balances,
+}
+
+/// Script line `line` could not be parsed
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Parses commands of `script`, returning them along with their line numbers
+pub fn parse(script: &str) -> Result<Vec<(usize, Command)>, ParseError> {
+    let mut commands = Vec::new();
+
+    for (index, line) in script.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.split('#').next().unwrap_or_default();
+
+        let command = parse_command(line).map_err(|message| ParseError {
+            line: line_number,
+            message,
+        })?;
+
+        if let Some(command) = command {
+            commands.push((line_number, command));
+        }
+    }
+
+    Ok(commands)
+}
+
+fn parse_command(line: &str) -> Result<Option<Command>, String> {
+    let mut args = line.split_whitespace();
+
+    let name = match args.next() {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+
+    let command = match name {
+        "balance" => Command::Balance {
+            account: next(&mut args, "account")?,
+            amount: number(&next(&mut args, "amount")?)?,
+        },
+        "game" => {
+            let white = next(&mut args, "white")?;
+            let black = next(&mut args, "black")?;
+            let stake = number(&next(&mut args, "stake")?)?;
+            let base = number(&next(&mut args, "base")?)?;
+            let increment = number(&next(&mut args, "increment")?)?;
+            let fen: Vec<&str> = args.by_ref().collect();
+
+            Command::Game {
+                white,
+                black,
+                stake,
+                base,
+                increment,
+                fen: if fen.is_empty() {
+                    None
+                } else {
+                    Some(fen.join(" "))
+                },
+            }
+        }
+        "deposit" => Command::Deposit {
+            account: next(&mut args, "account")?,
+            amount: number(&next(&mut args, "amount")?)?,
+        },
+        "advance" => Command::Advance(number(&next(&mut args, "blocks")?)?),
+        "move" => {
+            let account = next(&mut args, "account")?;
+            let mov = next(&mut args, "move")?;
+
+            Command::Move {
+                account,
+                mov: mov
+                    .as_str()
+                    .try_into()
+                    .map_err(|_| format!("invalid move {}", mov))?,
+            }
+        }
+        "offer_draw" => Command::OfferDraw {
+            account: next(&mut args, "account")?,
+            offer: match args.next() {
+                None | Some("true") => true,
+                Some("false") => false,
+                Some(value) => return Err(format!("invalid offer {}", value)),
+            },
+        },
+        "decline_draw" => Command::DeclineDraw(next(&mut args, "account")?),
+        "resign" => Command::Resign(next(&mut args, "account")?),
+        "report_abandonment" => Command::ReportAbandonment(next(&mut args, "account")?),
+        "pgn" => Command::Pgn(next(&mut args, "path")?),
+        "status" => Command::Status,
+        "balances" => Command::Balances,
+        _ => return Err(format!("unknown command {}", name)),
+    };
+
+    if let Some(arg) = args.next() {
+        return Err(format!("unexpected argument {}", arg));
+    }
+
+    Ok(Some(command))
+}
+
+fn next(args: &mut SplitWhitespace, what: &str) -> Result<String, String> {
+    args.next()
+        .map(String::from)
+        .ok_or_else(|| format!("{} argument missing", what))
+}
+
+fn number<T: core::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid number {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_script() {
+        let script = "
+            # Fools mate
+            game alice bob 100 10 1 rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1
+            deposit alice 100
+            advance 2
+            move alice f2f3 # first move
+            offer_draw bob false
+        ";
+
+        let commands = parse(script).unwrap();
+
+        assert_eq!(commands.len(), 5);
+        assert_eq!(
+            commands[0],
+            (
+                3,
+                Command::Game {
+                    white: "alice".into(),
+                    black: "bob".into(),
+                    stake: 100,
+                    base: 10,
+                    increment: 1,
+                    fen: Some(dotchess::Game::FEN_NEW_GAME.into()),
+                }
+            )
+        );
+        assert_eq!(
+            commands[3].1,
+            Command::Move {
+                account: "alice".into(),
+                mov: "f2f3".try_into().unwrap(),
+            }
+        );
+        assert_eq!(
+            commands[4].1,
+            Command::OfferDraw {
+                account: "bob".into(),
+                offer: false,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            parse("advance\n"),
+            Err(ParseError {
+                line: 1,
+                message: "blocks argument missing".into(),
+            })
+        );
+        assert_eq!(
+            parse("status\nresign alice bob").unwrap_err().message,
+            "unexpected argument bob"
+        );
+        assert_eq!(
+            parse("move alice e2").unwrap_err().message,
+            "invalid move e2"
+        );
+    }
+}
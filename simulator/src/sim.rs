@@ -0,0 +1,326 @@
+//! Running contract calls in the off-chain environment
+
+use crate::event;
+use crate::pgn;
+use crate::script::Command;
+use dotchess::{ClockUnit, DotChess, Game, TimeControl};
+use ink_env::call::Selector;
+use ink_env::test::{self, CallData, DefaultAccounts};
+use ink_env::{AccountId, DefaultEnvironment};
+
+type Balance = <DefaultEnvironment as ink_env::Environment>::Balance;
+
+/// Time control of games instantiated by replaying a PGN
+const PGN_BLOCK_BASE: u32 = 1000;
+const PGN_BLOCK_INCREMENT: u32 = 10;
+
+pub struct Simulator {
+    accounts: Vec<(&'static str, AccountId)>,
+    contract: AccountId,
+    chess: Option<DotChess>,
+    events_seen: usize,
+}
+
+impl Simulator {
+    pub fn new(accounts: DefaultAccounts<DefaultEnvironment>) -> Self {
+        Self {
+            accounts: vec![
+                ("alice", accounts.alice),
+                ("bob", accounts.bob),
+                ("charlie", accounts.charlie),
+                ("django", accounts.django),
+                ("eve", accounts.eve),
+                ("frank", accounts.frank),
+            ],
+            contract: ink_env::account_id::<DefaultEnvironment>().unwrap(),
+            chess: None,
+            events_seen: 0,
+        }
+    }
+
+    /// Returns game instantiated by the script
+    pub fn chess(&self) -> Option<&DotChess> {
+        self.chess.as_ref()
+    }
+
+    /// Runs `command`, returning lines to print. Failed calls and events
+    /// emitted by the command are printed as well
+    pub fn run(&mut self, command: &Command) -> Vec<String> {
+        let mut lines = match self.execute(command) {
+            Ok(lines) => lines,
+            Err(error) => vec![format!("error: {}", error)],
+        };
+
+        lines.extend(self.new_events());
+
+        lines
+    }
+
+    fn execute(&mut self, command: &Command) -> Result<Vec<String>, String> {
+        match command {
+            Command::Balance { account, amount } => {
+                let account = self.account(account)?;
+
+                test::set_account_balance::<DefaultEnvironment>(account, *amount)
+                    .map_err(|error| format!("{:?}", error))?;
+            }
+            Command::Game {
+                white,
+                black,
+                stake,
+                base,
+                increment,
+                fen,
+            } => {
+                let white = self.account(white)?;
+                let black = self.account(black)?;
+                let fen = fen.clone().unwrap_or_else(|| Game::FEN_NEW_GAME.into());
+
+                self.instantiate(white, black, *stake, *base, *increment, fen)?;
+            }
+            Command::Deposit { account, amount } => {
+                let account = self.account(account)?;
+                let balance = self.balance(account);
+
+                if balance < *amount {
+                    return Err("insufficient balance".into());
+                }
+
+                self.call(account, *amount, |chess| chess.deposit())?;
+
+                // Off-chain environment does not move transferred value
+                let contract_balance = self.balance(self.contract);
+
+                test::set_account_balance::<DefaultEnvironment>(account, balance - amount)
+                    .map_err(|error| format!("{:?}", error))?;
+                test::set_account_balance::<DefaultEnvironment>(
+                    self.contract,
+                    contract_balance + amount,
+                )
+                .map_err(|error| format!("{:?}", error))?;
+            }
+            Command::Advance(blocks) => {
+                for _ in 0..*blocks {
+                    test::advance_block::<DefaultEnvironment>()
+                        .map_err(|error| format!("{:?}", error))?;
+                }
+            }
+            Command::Move { account, mov } => {
+                let account = self.account(account)?;
+                let mov = *mov;
+
+                self.call(account, 0, |chess| chess.make_move(mov))?;
+            }
+            Command::OfferDraw { account, offer } => {
+                let account = self.account(account)?;
+                let offer = *offer;
+
+                self.call(account, 0, |chess| chess.offer_draw(offer))?;
+            }
+            Command::DeclineDraw(account) => {
+                let account = self.account(account)?;
+
+                self.call(account, 0, |chess| chess.decline_draw())?;
+            }
+            Command::Resign(account) => {
+                let account = self.account(account)?;
+
+                self.call(account, 0, |chess| chess.resign())?;
+            }
+            Command::ReportAbandonment(account) => {
+                let account = self.account(account)?;
+
+                self.call(account, 0, |chess| chess.report_abandonment())?;
+            }
+            Command::Pgn(path) => return self.replay_pgn(path),
+            Command::Status => {
+                let chess = self.chess.as_ref().ok_or("no game instantiated")?;
+
+                return Ok(vec![
+                    format!("status: {:?}", chess.status()),
+                    format!(
+                        "fen: {}",
+                        chess.fen().map_err(|error| format!("{:?}", error))?
+                    ),
+                ]);
+            }
+            Command::Balances => {
+                let mut lines: Vec<String> = self
+                    .accounts
+                    .iter()
+                    .map(|(name, account)| format!("{}: {}", name, self.balance(*account)))
+                    .collect();
+
+                lines.push(format!("contract: {}", self.balance(self.contract)));
+
+                return Ok(lines);
+            }
+        }
+
+        Ok(Vec::new())
+    }
+
+    /// Plays moves of PGN file at `path`, a game of alice and bob is instantiated
+    /// from the PGN position unless there is one already
+    fn replay_pgn(&mut self, path: &str) -> Result<Vec<String>, String> {
+        let input = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+        let pgn = pgn::parse(&input);
+
+        if self.chess.is_none() {
+            let (alice, bob) = (self.accounts[0].1, self.accounts[1].1);
+            let fen = pgn.fen.unwrap_or_else(|| Game::FEN_NEW_GAME.into());
+
+            self.instantiate(alice, bob, 0, PGN_BLOCK_BASE, PGN_BLOCK_INCREMENT, fen)?;
+        }
+
+        let mut lines = Vec::new();
+
+        for san in pgn.moves.iter() {
+            let chess = self.chess.as_ref().ok_or("no game instantiated")?;
+            let fen = chess.fen().map_err(|error| format!("{:?}", error))?;
+            let game = Game::new(&fen).map_err(|error| format!("{:?}", error))?;
+            let (white, black) = chess.players();
+
+            let caller = match chess.side_to_move() {
+                dotchess::Side::White => white,
+                dotchess::Side::Black => black,
+            };
+
+            let mov = pgn::find_move(&game, san).ok_or_else(|| format!("illegal move {}", san))?;
+
+            self.call(caller, 0, |chess| chess.make_move(mov))?;
+
+            lines.push(format!("move {}", san));
+            lines.extend(self.new_events());
+        }
+
+        Ok(lines)
+    }
+
+    fn instantiate(
+        &mut self,
+        white: AccountId,
+        black: AccountId,
+        stake: Balance,
+        block_base: u32,
+        block_increment: u32,
+        fen: String,
+    ) -> Result<(), String> {
+        Game::new(&fen).map_err(|error| format!("invalid FEN: {:?}", error))?;
+
+        let time_control = TimeControl::Classic {
+            base: block_base,
+            increment: block_increment,
+        };
+
+        self.chess = Some(DotChess::with_stake(
+            white,
+            black,
+            time_control,
+            ClockUnit::Blocks,
+            stake,
+            fen,
+        ));
+
+        Ok(())
+    }
+
+    /// Calls `message` of the game as `caller` transferring `value` along
+    fn call<T, F>(&mut self, caller: AccountId, value: Balance, message: F) -> Result<T, String>
+    where
+        F: FnOnce(&mut DotChess) -> dotchess::Result<T>,
+    {
+        let chess = self.chess.as_mut().ok_or("no game instantiated")?;
+
+        test::push_execution_context::<DefaultEnvironment>(
+            caller,
+            self.contract,
+            1000000,
+            value,
+            CallData::new(Selector::new([0x00; 4])),
+        );
+
+        let result = message(chess);
+
+        test::pop_execution_context();
+
+        result.map_err(|error| format!("{:?}", error))
+    }
+
+    fn account(&self, name: &str) -> Result<AccountId, String> {
+        self.accounts
+            .iter()
+            .find(|(account_name, _)| *account_name == name)
+            .map(|(_, account)| *account)
+            .ok_or_else(|| format!("unknown account {}", name))
+    }
+
+    fn balance(&self, account: AccountId) -> Balance {
+        test::get_account_balance::<DefaultEnvironment>(account).unwrap_or_default()
+    }
+
+    /// Returns events emitted since the last call
+    fn new_events(&mut self) -> Vec<String> {
+        let events: Vec<String> = test::recorded_events()
+            .skip(self.events_seen)
+            .map(|recorded| format!("event: {}", event::describe(&recorded.data)))
+            .collect();
+
+        self.events_seen += events.len();
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::script;
+    use dotchess::{GameOverReason, Side, Status};
+
+    #[test]
+    fn fools_mate_pays_winner() {
+        test::run_test::<DefaultEnvironment, _>(|accounts| {
+            let alice = accounts.alice;
+            let mut simulator = Simulator::new(accounts);
+            let alice_balance = simulator.balance(alice);
+
+            let commands = script::parse(
+                "game alice bob 100 10 1
+                deposit alice 100
+                deposit bob 100
+                move alice f2f3
+                move bob e7e5
+                move bob d7d5
+                move alice g2g4
+                move bob d8h4",
+            )
+            .unwrap();
+
+            let output: Vec<String> = commands
+                .iter()
+                .flat_map(|(_, command)| simulator.run(command))
+                .collect();
+
+            assert_eq!(
+                output
+                    .iter()
+                    .filter(|line| line.starts_with("error"))
+                    .count(),
+                1
+            );
+            assert!(output.last().unwrap().starts_with("event: GameOver"));
+            assert_eq!(
+                simulator.chess().unwrap().status(),
+                Status::Finished {
+                    winner: Some(Side::Black),
+                    reason: GameOverReason::Checkmate,
+                }
+            );
+            assert_eq!(simulator.balance(alice), alice_balance - 100);
+
+            Ok(())
+        })
+        .unwrap();
+    }
+}
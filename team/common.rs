@@ -0,0 +1,42 @@
+use scale::{Decode, Encode};
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Encode, Decode, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+    /// Caller is not allowed to perform the action
+    InvalidCaller,
+    /// Transferred balance does not match the stake
+    InvalidDeposit,
+    /// Game is waiting for team members to deposit their stakes
+    GameNotFunded,
+    /// Game is no longer waiting for deposits
+    GameAlreadyFunded,
+    /// Funding deadline has passed, the game can only be aborted
+    FundingClosed,
+    /// Funding deadline has not passed yet
+    FundingOpen,
+    /// Game has already ended
+    GameAlreadyOver,
+    /// Voted move is not legal in the current position
+    IllegalMove,
+    /// Voting window of the current move has passed, the vote has to be closed
+    VotingClosed,
+    /// Voting window of the current move has not passed yet
+    VotingOpen,
+    /// Transfer of funds failed
+    TransferFailed,
+    Other,
+}
+
+impl core::convert::From<ink_env::Error> for Error {
+    fn from(error: ink_env::Error) -> Self {
+        match error {
+            ink_env::Error::TransferFailed | ink_env::Error::BelowSubsistenceThreshold => {
+                Self::TransferFailed
+            }
+            _ => Self::Other,
+        }
+    }
+}
@@ -0,0 +1,882 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod common;
+
+pub use crate::common::{Error, Result};
+pub use crate::team::{Candidate, TeamGame};
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod team {
+
+    use crate::common::{Error, Result};
+    use alloc::string::String;
+    use dotchess::{Game, GameOverReason, Mov, Side, Status, ZobristHash};
+    use ink_storage::collections::HashMap;
+    use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
+    use ink_storage::{Pack, Vec};
+    use scale::{Decode, Encode};
+
+    /// Event emitted when the `member` votes for `mov`, which then has `votes` votes
+    #[ink(event)]
+    pub struct MoveVoted {
+        #[ink(topic)]
+        member: AccountId,
+        mov: Mov,
+        votes: u32,
+    }
+
+    /// Event emitted when `mov` chosen by `votes` members of the `side` team is played,
+    /// `fen` is the position after the move
+    #[ink(event)]
+    pub struct MovePlayed {
+        side: Side,
+        mov: Mov,
+        votes: u32,
+        fen: String,
+    }
+
+    /// Event emitted when the `member` changes their resignation or draw vote
+    #[ink(event)]
+    pub struct TeamVote {
+        #[ink(topic)]
+        member: AccountId,
+        resign: bool,
+        draw: bool,
+    }
+
+    /// Event emitted when draw offer of the `side` team reaches (or loses) the quorum
+    #[ink(event)]
+    pub struct DrawOfferUpdate {
+        #[ink(topic)]
+        side: Side,
+        offer: bool,
+    }
+
+    #[ink(event)]
+    pub struct GameOver {
+        winner: Option<Side>,
+        reason: GameOverReason,
+    }
+
+    /// Move voted for by `votes` members of the team to move
+    #[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    pub struct Candidate {
+        pub mov: Mov,
+        pub votes: u32,
+    }
+
+    /// Team member, `ballot` is the move voted for paired with the ply it was voted for
+    #[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    struct Member {
+        side: Side,
+        deposited: bool,
+        resign: bool,
+        draw: bool,
+        ballot: Option<(u32, Mov)>,
+    }
+
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    struct Info {
+        stake: Balance,
+        /// Last block members can deposit in
+        funding_deadline: BlockNumber,
+        quorum: u8,
+        voting_window: BlockNumber,
+        /// Number of plies played
+        ply: u32,
+        /// Block the current voting round started in
+        vote_start: BlockNumber,
+        deposits: u32,
+        status: Status,
+        white_draw_offer: bool,
+        black_draw_offer: bool,
+    }
+
+    #[ink(storage)]
+    pub struct TeamGame {
+        /// Current position
+        game: Pack<Game>,
+        /// Members of both teams
+        members: HashMap<AccountId, Member>,
+        white_team: Vec<AccountId>,
+        black_team: Vec<AccountId>,
+        /// Moves voted for in the current voting round
+        candidates: Vec<Candidate>,
+        /// Zobrist hashes of positions since the last capture or pawn move
+        zobrist: Pack<alloc::vec::Vec<ZobristHash>>,
+        info: Pack<Info>,
+    }
+
+    impl TeamGame {
+        /// Initiates a consultation game of `white_team` and `black_team` from
+        /// position `fen`, every member deposits `stake` in the next `funding_window`
+        /// blocks before the game starts, otherwise the game can be aborted
+        ///
+        /// Members of the team to move vote for moves for `voting_window` blocks, a move
+        /// voted for by more than half of the team is played right away. Resignation and
+        /// draw offers need votes of `quorum` percent of the team.
+        #[ink(constructor)]
+        pub fn new(
+            white_team: alloc::vec::Vec<AccountId>,
+            black_team: alloc::vec::Vec<AccountId>,
+            stake: Balance,
+            funding_window: BlockNumber,
+            voting_window: BlockNumber,
+            quorum: u8,
+            fen: String,
+        ) -> Self {
+            assert!(
+                !white_team.is_empty() && !black_team.is_empty(),
+                "teams must not be empty"
+            );
+            assert!(quorum > 0 && quorum <= 100, "quorum must be a percentage");
+
+            let game = Game::new(fen.as_str()).unwrap();
+            let mut members = HashMap::new();
+
+            for (side, team) in [(Side::White, &white_team), (Side::Black, &black_team)].iter() {
+                for account in team.iter() {
+                    let member = Member {
+                        side: *side,
+                        deposited: stake == 0,
+                        resign: false,
+                        draw: false,
+                        ballot: None,
+                    };
+
+                    assert!(
+                        members.insert(*account, member).is_none(),
+                        "account can be a member only once"
+                    );
+                }
+            }
+
+            let info = Info {
+                stake,
+                funding_deadline: Self::env().block_number() + funding_window,
+                quorum,
+                voting_window,
+                ply: 0,
+                vote_start: Self::env().block_number(),
+                deposits: 0,
+                status: if stake == 0 {
+                    Status::Active
+                } else {
+                    Status::Funding
+                },
+                white_draw_offer: false,
+                black_draw_offer: false,
+            };
+
+            let zobrist = game.zobrist();
+
+            Self {
+                game: Pack::new(game),
+                members,
+                white_team: white_team.into_iter().collect(),
+                black_team: black_team.into_iter().collect(),
+                candidates: Vec::new(),
+                zobrist: Pack::new(alloc::vec![zobrist]),
+                info: Pack::new(info),
+            }
+        }
+
+        /// Deposits stake of the caller, the game starts once all members deposit
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) -> Result<()> {
+            match self.info.status {
+                Status::Funding => {}
+                Status::Active => return Err(Error::GameAlreadyFunded),
+                Status::Finished { .. } => return Err(Error::GameAlreadyOver),
+            }
+
+            if self.env().block_number() > self.info.funding_deadline {
+                return Err(Error::FundingClosed);
+            }
+
+            if self.env().transferred_balance() != self.info.stake {
+                return Err(Error::InvalidDeposit);
+            }
+
+            let caller = self.env().caller();
+            let member = self.member(caller)?;
+
+            if member.deposited {
+                return Err(Error::InvalidCaller);
+            }
+
+            self.members.get_mut(&caller).unwrap().deposited = true;
+            self.info.deposits += 1;
+
+            if self.info.deposits == self.members.len() {
+                self.info.status = Status::Active;
+                self.info.vote_start = self.env().block_number();
+            }
+
+            Ok(())
+        }
+
+        /// Aborts the game after the funding deadline has passed without all deposits,
+        /// returning the deposits
+        #[ink(message)]
+        pub fn abort(&mut self) -> Result<()> {
+            match self.info.status {
+                Status::Funding => {}
+                Status::Active => return Err(Error::GameAlreadyFunded),
+                Status::Finished { .. } => return Err(Error::GameAlreadyOver),
+            }
+
+            if self.env().block_number() <= self.info.funding_deadline {
+                return Err(Error::FundingOpen);
+            }
+
+            self.finish(None, GameOverReason::Aborted)
+        }
+
+        /// Votes for `mov` of the team to move, replacing previous vote of the caller
+        ///
+        /// The move is played right away once more than half of the team votes for it
+        #[ink(message)]
+        pub fn vote_move(&mut self, mov: Mov) -> Result<()> {
+            self.ensure_active()?;
+
+            let caller = self.env().caller();
+            let member = self.member(caller)?;
+            let side = self.game.side_next_in_turn();
+
+            if member.side != side {
+                return Err(Error::InvalidCaller);
+            }
+
+            if self.voting_closed() {
+                return Err(Error::VotingClosed);
+            }
+
+            self.game.make_move(&mov).map_err(|_| Error::IllegalMove)?;
+
+            if let Some((ply, previous)) = member.ballot {
+                if ply == self.info.ply {
+                    if previous == mov {
+                        return Ok(());
+                    }
+
+                    self.add_vote(previous, false);
+                }
+            }
+
+            let votes = self.add_vote(mov, true);
+
+            self.members.get_mut(&caller).unwrap().ballot = Some((self.info.ply, mov));
+
+            self.env().emit_event(MoveVoted {
+                member: caller,
+                mov,
+                votes,
+            });
+
+            if votes * 2 > self.team_size(side) {
+                return self.play_move(mov);
+            }
+
+            Ok(())
+        }
+
+        /// Closes the voting round after its window has passed, playing the move with
+        /// the most votes. Ties are broken by the lowest encoded move (see `Mov::encode`).
+        ///
+        /// The team to move loses on time if nobody voted.
+        #[ink(message)]
+        pub fn close_vote(&mut self) -> Result<()> {
+            self.ensure_active()?;
+
+            if !self.voting_closed() {
+                return Err(Error::VotingOpen);
+            }
+
+            match self.winning_candidate() {
+                Some(candidate) => self.play_move(candidate.mov),
+                None => {
+                    let side = self.game.side_next_in_turn();
+
+                    let winner = if self.game.side_has_sufficient_mating_material(side.flip()) {
+                        Some(side.flip())
+                    } else {
+                        None
+                    };
+
+                    self.finish(winner, GameOverReason::Abandonment)
+                }
+            }
+        }
+
+        /// Votes for resignation of the callers team if `resign` is true, withdraws
+        /// the vote otherwise. The team resigns once the quorum is reached.
+        #[ink(message)]
+        pub fn vote_resign(&mut self, resign: bool) -> Result<()> {
+            self.ensure_active()?;
+
+            let caller = self.env().caller();
+            let member = self.member(caller)?;
+
+            self.members.get_mut(&caller).unwrap().resign = resign;
+
+            self.env().emit_event(TeamVote {
+                member: caller,
+                resign,
+                draw: member.draw,
+            });
+
+            let side = member.side;
+
+            if self.quorum_reached(side, |member| member.resign) {
+                return self.finish(Some(side.flip()), GameOverReason::Resignation);
+            }
+
+            Ok(())
+        }
+
+        /// Votes for a draw offer of the callers team if `offer` is true, withdraws
+        /// the vote otherwise. The team offers a draw while the quorum is reached,
+        /// the game is drawn once both teams offer a draw.
+        #[ink(message)]
+        pub fn vote_draw(&mut self, offer: bool) -> Result<()> {
+            self.ensure_active()?;
+
+            let caller = self.env().caller();
+            let member = self.member(caller)?;
+
+            self.members.get_mut(&caller).unwrap().draw = offer;
+
+            self.env().emit_event(TeamVote {
+                member: caller,
+                resign: member.resign,
+                draw: offer,
+            });
+
+            let side = member.side;
+            let team_offer = self.quorum_reached(side, |member| member.draw);
+
+            if team_offer != self.team_draw_offer(side) {
+                match side {
+                    Side::White => self.info.white_draw_offer = team_offer,
+                    Side::Black => self.info.black_draw_offer = team_offer,
+                }
+
+                self.env().emit_event(DrawOfferUpdate {
+                    side,
+                    offer: team_offer,
+                });
+            }
+
+            if self.info.white_draw_offer && self.info.black_draw_offer {
+                return self.finish(None, GameOverReason::DrawAgreement);
+            }
+
+            Ok(())
+        }
+
+        /// Returns FEN string representation of the board
+        #[ink(message)]
+        pub fn fen(&self) -> Result<String> {
+            self.game.fen().map_err(|_| Error::Other)
+        }
+
+        /// Returns game status
+        #[ink(message)]
+        pub fn status(&self) -> Status {
+            self.info.status
+        }
+
+        /// Returns side whose team votes for the next move
+        #[ink(message)]
+        pub fn side_to_move(&self) -> Side {
+            self.game.side_next_in_turn()
+        }
+
+        /// Returns members of the `side` team
+        #[ink(message)]
+        pub fn team(&self, side: Side) -> alloc::vec::Vec<AccountId> {
+            self.team_members(side).iter().copied().collect()
+        }
+
+        /// Returns moves voted for in the current voting round
+        #[ink(message)]
+        pub fn candidates(&self) -> alloc::vec::Vec<Candidate> {
+            self.candidates
+                .iter()
+                .filter(|candidate| candidate.votes > 0)
+                .copied()
+                .collect()
+        }
+
+        /// Returns last block members can deposit their stakes in
+        #[ink(message)]
+        pub fn funding_deadline(&self) -> BlockNumber {
+            self.info.funding_deadline
+        }
+
+        /// Returns last block of the current voting round
+        #[ink(message)]
+        pub fn voting_deadline(&self) -> BlockNumber {
+            self.info.vote_start + self.info.voting_window
+        }
+
+        /// Returns true if the `side` team offers a draw
+        #[ink(message)]
+        pub fn team_draw_offer(&self, side: Side) -> bool {
+            match side {
+                Side::White => self.info.white_draw_offer,
+                Side::Black => self.info.black_draw_offer,
+            }
+        }
+
+        /// Adds (or removes) a vote for `mov`, returning number of its votes
+        fn add_vote(&mut self, mov: Mov, add: bool) -> u32 {
+            let index = self
+                .candidates
+                .iter()
+                .position(|candidate| candidate.mov == mov);
+
+            let index = match index {
+                Some(index) => index as u32,
+                None => {
+                    self.candidates.push(Candidate { mov, votes: 0 });
+                    self.candidates.len() - 1
+                }
+            };
+
+            let candidate = self.candidates.get_mut(index).unwrap();
+
+            if add {
+                candidate.votes += 1;
+            } else {
+                candidate.votes -= 1;
+            }
+
+            candidate.votes
+        }
+
+        fn winning_candidate(&self) -> Option<Candidate> {
+            self.candidates
+                .iter()
+                .filter(|candidate| candidate.votes > 0)
+                .fold(None, |best: Option<Candidate>, candidate| match best {
+                    Some(best)
+                        if best.votes > candidate.votes
+                            || (best.votes == candidate.votes
+                                && best.mov.encode() < candidate.mov.encode()) =>
+                    {
+                        Some(best)
+                    }
+                    _ => Some(*candidate),
+                })
+        }
+
+        /// Plays `mov` for the team to move and starts the next voting round,
+        /// the game ends by the same rules as a DotChess game
+        fn play_move(&mut self, mov: Mov) -> Result<()> {
+            let side = self.game.side_next_in_turn();
+            let votes = self
+                .candidates
+                .iter()
+                .find(|candidate| candidate.mov == mov)
+                .map_or(0, |candidate| candidate.votes);
+
+            let game = self.game.make_move(&mov).map_err(|_| Error::IllegalMove)?;
+
+            // Positions before a capture or pawn move cannot repeat
+            if game.halfmove_clock() == 0 {
+                self.zobrist.clear();
+            }
+
+            self.zobrist.push(game.zobrist());
+            self.game = Pack::new(game);
+            self.info.ply += 1;
+            self.info.vote_start = self.env().block_number();
+            self.candidates.clear();
+
+            self.env().emit_event(MovePlayed {
+                side,
+                mov,
+                votes,
+                fen: self.fen()?,
+            });
+
+            if let Some((winner, reason)) = self.game.position_result() {
+                return self.finish(winner, reason);
+            }
+
+            let zobrist = self.game.zobrist();
+            let repetitions = self.zobrist.iter().filter(|z| **z == zobrist).count() as u32;
+
+            if let Some(reason) = self.game.forced_draw(repetitions) {
+                return self.finish(None, reason);
+            }
+
+            Ok(())
+        }
+
+        /// Ends the game, the pot is split equally among the winning team members
+        /// or the deposited stakes are returned on a draw
+        fn finish(&mut self, winner: Option<Side>, reason: GameOverReason) -> Result<()> {
+            let stake = self.info.stake;
+
+            self.info.status = Status::Finished { winner, reason };
+
+            match winner {
+                Some(side) => {
+                    let team = self.team(side);
+                    let pot = stake * self.members.len() as Balance;
+                    let share = pot / team.len() as Balance;
+
+                    // Remainder of the split goes to the first member
+                    let remainder = pot - share * team.len() as Balance;
+
+                    for (index, member) in team.into_iter().enumerate() {
+                        let amount = if index == 0 { share + remainder } else { share };
+
+                        self.pay(member, amount)?;
+                    }
+                }
+                None => {
+                    for side in [Side::White, Side::Black].iter() {
+                        for member in self.team(*side) {
+                            if self.member(member)?.deposited {
+                                self.pay(member, stake)?;
+                            }
+                        }
+                    }
+                }
+            }
+
+            self.env().emit_event(GameOver { winner, reason });
+
+            Ok(())
+        }
+
+        fn pay(&mut self, account: AccountId, amount: Balance) -> Result<()> {
+            if amount > 0 {
+                self.env().transfer(account, amount)?;
+            }
+
+            Ok(())
+        }
+
+        fn member(&self, account: AccountId) -> Result<Member> {
+            self.members
+                .get(&account)
+                .copied()
+                .ok_or(Error::InvalidCaller)
+        }
+
+        fn team_members(&self, side: Side) -> &Vec<AccountId> {
+            match side {
+                Side::White => &self.white_team,
+                Side::Black => &self.black_team,
+            }
+        }
+
+        fn team_size(&self, side: Side) -> u32 {
+            self.team_members(side).len()
+        }
+
+        /// Returns true if at least `quorum` percent of the `side` team vote as `vote`
+        fn quorum_reached(&self, side: Side, vote: fn(&Member) -> bool) -> bool {
+            let votes = self
+                .team_members(side)
+                .iter()
+                .filter(|account| self.members.get(*account).map_or(false, vote))
+                .count() as u32;
+
+            votes * 100 >= self.info.quorum as u32 * self.team_size(side)
+        }
+
+        fn voting_closed(&self) -> bool {
+            self.env().block_number() > self.voting_deadline()
+        }
+
+        fn ensure_active(&self) -> Result<()> {
+            match self.info.status {
+                Status::Funding => Err(Error::GameNotFunded),
+                Status::Active => Ok(()),
+                Status::Finished { .. } => Err(Error::GameAlreadyOver),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use core::convert::TryInto;
+        use ink_lang as ink;
+
+        fn accounts() -> ink_env::test::DefaultAccounts<ink_env::DefaultEnvironment> {
+            ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().unwrap()
+        }
+
+        fn set_caller(caller: AccountId, transferred: Balance) {
+            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
+                caller,
+                contract(),
+                1000000,
+                transferred,
+                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])),
+            );
+        }
+
+        fn contract() -> AccountId {
+            ink_env::account_id::<ink_env::DefaultEnvironment>().unwrap()
+        }
+
+        fn balance(account: AccountId) -> Balance {
+            ink_env::test::get_account_balance::<ink_env::DefaultEnvironment>(account).unwrap()
+        }
+
+        fn advance_blocks(n: u32) {
+            for _ in 0..n {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>().unwrap();
+            }
+        }
+
+        fn mov(mov: &str) -> Mov {
+            mov.try_into().unwrap()
+        }
+
+        fn team_game(
+            white_team: &[AccountId],
+            black_team: &[AccountId],
+            stake: Balance,
+            quorum: u8,
+        ) -> TeamGame {
+            TeamGame::new(
+                white_team.to_vec(),
+                black_team.to_vec(),
+                stake,
+                5,
+                10,
+                quorum,
+                Game::FEN_NEW_GAME.into(),
+            )
+        }
+
+        #[ink::test]
+        fn majority_vote_plays_move() {
+            let accounts = accounts();
+            let mut game = team_game(
+                &[accounts.alice, accounts.bob, accounts.charlie],
+                &[accounts.django],
+                0,
+                50,
+            );
+
+            set_caller(accounts.django, 0);
+            assert_eq!(game.vote_move(mov("e7e5")), Err(Error::InvalidCaller));
+
+            set_caller(accounts.alice, 0);
+            assert_eq!(game.vote_move(mov("e2e5")), Err(Error::IllegalMove));
+            game.vote_move(mov("e2e4")).unwrap();
+
+            set_caller(accounts.bob, 0);
+            game.vote_move(mov("d2d4")).unwrap();
+
+            assert_eq!(game.side_to_move(), Side::White);
+            assert_eq!(game.candidates().len(), 2);
+
+            set_caller(accounts.charlie, 0);
+            game.vote_move(mov("e2e4")).unwrap();
+
+            assert_eq!(game.side_to_move(), Side::Black);
+            assert!(game.candidates().is_empty());
+
+            // Sole member of a team is its majority
+            set_caller(accounts.django, 0);
+            game.vote_move(mov("e7e5")).unwrap();
+
+            assert_eq!(
+                game.fen().unwrap(),
+                "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2"
+            );
+        }
+
+        #[ink::test]
+        fn tie_is_broken_when_vote_closes() {
+            let accounts = accounts();
+            let mut game = team_game(&[accounts.alice, accounts.bob], &[accounts.charlie], 0, 50);
+
+            set_caller(accounts.alice, 0);
+            game.vote_move(mov("e2e4")).unwrap();
+
+            set_caller(accounts.bob, 0);
+            game.vote_move(mov("d2d4")).unwrap();
+
+            assert_eq!(game.close_vote(), Err(Error::VotingOpen));
+
+            advance_blocks(11);
+
+            assert_eq!(game.vote_move(mov("c2c4")), Err(Error::VotingClosed));
+
+            game.close_vote().unwrap();
+
+            assert_eq!(
+                game.fen().unwrap(),
+                "rnbqkbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBQKBNR b KQkq d3 0 1"
+            );
+
+            // Black team does not vote and loses on time
+            advance_blocks(11);
+            game.close_vote().unwrap();
+
+            assert_eq!(
+                game.status(),
+                Status::Finished {
+                    winner: Some(Side::White),
+                    reason: GameOverReason::Abandonment,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn resignation_needs_quorum_and_pays_team() {
+            let accounts = accounts();
+            let mut game = team_game(
+                &[accounts.alice, accounts.bob],
+                &[accounts.charlie],
+                10,
+                100,
+            );
+
+            for member in &[accounts.alice, accounts.bob, accounts.charlie] {
+                set_caller(*member, 10);
+                game.deposit().unwrap();
+            }
+
+            // Off-chain environment does not move transferred value
+            ink_env::test::set_account_balance::<ink_env::DefaultEnvironment>(contract(), 30)
+                .unwrap();
+
+            let charlie_balance = balance(accounts.charlie);
+
+            set_caller(accounts.alice, 0);
+            game.vote_resign(true).unwrap();
+
+            assert_eq!(game.status(), Status::Active);
+
+            set_caller(accounts.bob, 0);
+            game.vote_resign(true).unwrap();
+
+            assert_eq!(
+                game.status(),
+                Status::Finished {
+                    winner: Some(Side::Black),
+                    reason: GameOverReason::Resignation,
+                }
+            );
+            assert_eq!(balance(accounts.charlie), charlie_balance + 30);
+        }
+
+        #[ink::test]
+        fn unfunded_game_is_aborted_after_deadline() {
+            let accounts = accounts();
+            let mut game = team_game(&[accounts.alice, accounts.bob], &[accounts.charlie], 10, 50);
+
+            set_caller(accounts.alice, 10);
+            game.deposit().unwrap();
+
+            // Off-chain environment does not move transferred value
+            ink_env::test::set_account_balance::<ink_env::DefaultEnvironment>(contract(), 10)
+                .unwrap();
+
+            let alice_balance = balance(accounts.alice);
+            let bob_balance = balance(accounts.bob);
+
+            assert_eq!(game.abort(), Err(Error::FundingOpen));
+
+            advance_blocks(6);
+
+            set_caller(accounts.bob, 10);
+            assert_eq!(game.deposit(), Err(Error::FundingClosed));
+
+            set_caller(accounts.django, 0);
+            game.abort().unwrap();
+
+            assert_eq!(
+                game.status(),
+                Status::Finished {
+                    winner: None,
+                    reason: GameOverReason::Aborted,
+                }
+            );
+            assert_eq!(balance(accounts.alice), alice_balance + 10);
+            assert_eq!(balance(accounts.bob), bob_balance);
+            assert_eq!(game.abort(), Err(Error::GameAlreadyOver));
+        }
+
+        #[ink::test]
+        fn fivefold_repetition_draws() {
+            let accounts = accounts();
+            let mut game = team_game(&[accounts.alice], &[accounts.bob], 0, 50);
+
+            for _ in 0..4 {
+                for (member, m) in [
+                    (accounts.alice, "g1f3"),
+                    (accounts.bob, "g8f6"),
+                    (accounts.alice, "f3g1"),
+                    (accounts.bob, "f6g8"),
+                ]
+                .iter()
+                {
+                    assert_eq!(game.status(), Status::Active);
+
+                    set_caller(*member, 0);
+                    game.vote_move(mov(m)).unwrap();
+                }
+            }
+
+            assert_eq!(
+                game.status(),
+                Status::Finished {
+                    winner: None,
+                    reason: GameOverReason::FivefoldRepetition,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn both_teams_agree_to_draw() {
+            let accounts = accounts();
+            let mut game = team_game(&[accounts.alice, accounts.bob], &[accounts.charlie], 0, 50);
+
+            set_caller(accounts.alice, 0);
+            game.vote_draw(true).unwrap();
+
+            assert!(game.team_draw_offer(Side::White));
+
+            set_caller(accounts.charlie, 0);
+            game.vote_draw(true).unwrap();
+
+            assert_eq!(
+                game.status(),
+                Status::Finished {
+                    winner: None,
+                    reason: GameOverReason::DrawAgreement,
+                }
+            );
+        }
+    }
+}
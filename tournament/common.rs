@@ -0,0 +1,54 @@
+use scale::{Decode, Encode};
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Encode, Decode, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+    /// Caller is not allowed to perform the action
+    InvalidCaller,
+    /// Tournament is no longer accepting entrants
+    RegistrationClosed,
+    /// Caller has already registered
+    AlreadyRegistered,
+    /// Tournament has reached the maximum number of entrants
+    TournamentFull,
+    /// Transferred balance does not match the entry fee
+    InvalidEntryFee,
+    /// Tournament needs at least two entrants to start
+    NotEnoughPlayers,
+    /// Tournament has not started yet
+    TournamentNotStarted,
+    /// Tournament has already ended
+    TournamentAlreadyOver,
+    /// Some games of the current round have not been recorded yet
+    RoundNotComplete,
+    /// All rounds have already been played
+    NoRoundsLeft,
+    /// Board index out of range of the current round
+    InvalidBoard,
+    /// Board already has a game contract
+    GameAlreadyCreated,
+    /// Board has no game contract yet
+    GameNotCreated,
+    /// Result of the board has already been recorded
+    ResultAlreadyRecorded,
+    /// Game has not ended yet
+    GameNotOver,
+    /// Game contract could not be instantiated
+    InstantiationFailed,
+    /// Transfer of funds failed
+    TransferFailed,
+    Other,
+}
+
+impl core::convert::From<ink_env::Error> for Error {
+    fn from(error: ink_env::Error) -> Self {
+        match error {
+            ink_env::Error::TransferFailed | ink_env::Error::BelowSubsistenceThreshold => {
+                Self::TransferFailed
+            }
+            _ => Self::Other,
+        }
+    }
+}
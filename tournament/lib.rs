@@ -0,0 +1,714 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod common;
+mod pairing;
+
+pub use crate::common::{Error, Result};
+pub use crate::pairing::{Outcome, Standing};
+pub use crate::tournament::{Board, Format, Phase, Tournament};
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod tournament {
+
+    use crate::common::{Error, Result};
+    use crate::pairing::{self, Outcome, Pairing, Played, Standing};
+    use dotchess::{ClockUnit, DotChess, Game, GameOverReason, Side, Status, TimeControl};
+    use ink_storage::collections::HashMap;
+    use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
+    use ink_storage::{Pack, Vec};
+    use scale::{Decode, Encode};
+
+    /// Event emitted when the `player` registers for the tournament
+    #[ink(event)]
+    pub struct PlayerRegistered {
+        #[ink(topic)]
+        player: AccountId,
+    }
+
+    /// Event emitted for every pairing of the `round`th round, `black` is none
+    /// when `white` gets a bye
+    #[ink(event)]
+    pub struct PairingCreated {
+        #[ink(topic)]
+        round: u32,
+        board: u32,
+        white: AccountId,
+        black: Option<AccountId>,
+    }
+
+    /// Event emitted when the `game` contract is assigned to the `board`
+    #[ink(event)]
+    pub struct GameRegistered {
+        board: u32,
+        #[ink(topic)]
+        game: AccountId,
+    }
+
+    /// Event emitted when result of the `board` is recorded
+    #[ink(event)]
+    pub struct ResultRecorded {
+        board: u32,
+        outcome: Outcome,
+    }
+
+    /// Event emitted when the `player` finishing at `place` (zero based)
+    /// receives `amount` from the prize pool
+    #[ink(event)]
+    pub struct PrizePaid {
+        #[ink(topic)]
+        player: AccountId,
+        place: u32,
+        amount: Balance,
+    }
+
+    /// Tournament format
+    #[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    pub enum Format {
+        /// Every entrant plays every other entrant once
+        RoundRobin,
+        /// Entrants with equal points are paired for `rounds` rounds
+        Swiss { rounds: u32 },
+    }
+
+    /// Tournament phase, `round` is zero based
+    #[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(feature = "std", derive(Debug, scale_info::TypeInfo, StorageLayout))]
+    pub enum Phase {
+        Registration,
+        Running { round: u32 },
+        Finished,
+    }
+
+    /// Game of a tournament round, players are entrant indices
+    ///
+    /// `black` is none when `white` gets a bye, `game` is the DotChess contract
+    /// the game is played in
+    #[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    pub struct Board {
+        pub round: u32,
+        pub white: u32,
+        pub black: Option<u32>,
+        pub game: Option<AccountId>,
+        pub outcome: Option<Outcome>,
+    }
+
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+    )]
+    struct Config {
+        format: Format,
+        entry_fee: Balance,
+        max_players: u32,
+        code_hash: Hash,
+        time_control: TimeControl,
+        clock_unit: ClockUnit,
+    }
+
+    #[ink(storage)]
+    pub struct Tournament {
+        /// Account that opened the tournament
+        organizer: AccountId,
+        /// Tournament settings
+        config: Pack<Config>,
+        /// Percentages of the prize pool paid to the best placed entrants
+        prizes: Vec<u8>,
+        /// Registered entrants
+        entrants: Vec<AccountId>,
+        /// Registered entrant accounts
+        registered: HashMap<AccountId, ()>,
+        /// Boards of all rounds
+        boards: Vec<Board>,
+        /// Index of the first board of every round
+        round_start: Vec<u32>,
+        /// Current phase
+        phase: Pack<Phase>,
+    }
+
+    impl Tournament {
+        /// Opens registration of a tournament with `entry_fee`, games are instantiated from
+        /// the DotChess contract `code_hash`
+        ///
+        /// After the last round the prize pool is distributed by `prizes`, which lists
+        /// percentages for the first, second, ... place. Anything left is paid to the organizer.
+        #[ink(constructor)]
+        #[allow(clippy::too_many_arguments)]
+        pub fn new(
+            format: Format,
+            entry_fee: Balance,
+            max_players: u32,
+            code_hash: Hash,
+            time_control: TimeControl,
+            clock_unit: ClockUnit,
+            prizes: alloc::vec::Vec<u8>,
+        ) -> Self {
+            assert!(
+                prizes.iter().map(|share| *share as u32).sum::<u32>() <= 100,
+                "prizes must not exceed 100 percent"
+            );
+
+            Self {
+                organizer: Self::env().caller(),
+                config: Pack::new(Config {
+                    format,
+                    entry_fee,
+                    max_players,
+                    code_hash,
+                    time_control,
+                    clock_unit,
+                }),
+                prizes: prizes.into_iter().collect(),
+                entrants: Vec::new(),
+                registered: HashMap::new(),
+                boards: Vec::new(),
+                round_start: Vec::new(),
+                phase: Pack::new(Phase::Registration),
+            }
+        }
+
+        /// Registers the caller, transferred balance must equal the entry fee
+        #[ink(message, payable)]
+        pub fn register(&mut self) -> Result<()> {
+            if *self.phase != Phase::Registration {
+                return Err(Error::RegistrationClosed);
+            }
+
+            if self.env().transferred_balance() != self.config.entry_fee {
+                return Err(Error::InvalidEntryFee);
+            }
+
+            let caller = self.env().caller();
+
+            if self.registered.contains_key(&caller) {
+                return Err(Error::AlreadyRegistered);
+            }
+
+            if self.entrants.len() >= self.config.max_players {
+                return Err(Error::TournamentFull);
+            }
+
+            self.registered.insert(caller, ());
+            self.entrants.push(caller);
+            self.env().emit_event(PlayerRegistered { player: caller });
+
+            Ok(())
+        }
+
+        /// Pairs the next round
+        ///
+        /// The first round closes registration and can only be started by the organizer,
+        /// following rounds can be started by anyone once all results are recorded
+        #[ink(message)]
+        pub fn start_round(&mut self) -> Result<()> {
+            let round = match *self.phase {
+                Phase::Registration => {
+                    if self.env().caller() != self.organizer {
+                        return Err(Error::InvalidCaller);
+                    }
+
+                    if self.entrants.len() < 2 {
+                        return Err(Error::NotEnoughPlayers);
+                    }
+
+                    0
+                }
+                Phase::Running { round } => {
+                    if !self.round_complete(round) {
+                        return Err(Error::RoundNotComplete);
+                    }
+
+                    round + 1
+                }
+                Phase::Finished => return Err(Error::TournamentAlreadyOver),
+            };
+
+            if round >= self.rounds() {
+                return Err(Error::NoRoundsLeft);
+            }
+
+            let players = self.entrants.len();
+            let pairings = match self.config.format {
+                Format::RoundRobin => pairing::round_robin(players, round),
+                Format::Swiss { .. } => pairing::swiss(players, &self.played()),
+            };
+
+            self.round_start.push(self.boards.len());
+
+            for Pairing { white, black } in pairings {
+                let board = self.boards.len();
+
+                // Bye counts as a win
+                let outcome = match black {
+                    Some(_) => None,
+                    None => Some(Outcome::WhiteWins),
+                };
+
+                self.boards.push(Board {
+                    round,
+                    white,
+                    black,
+                    game: None,
+                    outcome,
+                });
+
+                self.env().emit_event(PairingCreated {
+                    round,
+                    board,
+                    white: self.entrant(white),
+                    black: black.map(|black| self.entrant(black)),
+                });
+            }
+
+            *self.phase = Phase::Running { round };
+
+            Ok(())
+        }
+
+        /// Instantiates the DotChess game for the `board`, transferred balance
+        /// is passed on as the game contract endowment
+        #[ink(message, payable)]
+        pub fn create_game(&mut self, board: u32, salt: [u8; 4]) -> Result<AccountId> {
+            let entry = self.pending_board(board)?;
+
+            let white = self.entrant(entry.white);
+            let black = self.entrant(entry.black.ok_or(Error::InvalidBoard)?);
+
+            let game = DotChess::with_time_control(
+                white,
+                black,
+                self.config.time_control,
+                self.config.clock_unit,
+                Game::FEN_NEW_GAME.into(),
+            )
+            .endowment(self.env().transferred_balance())
+            .code_hash(self.config.code_hash)
+            .salt_bytes(salt)
+            .instantiate()
+            .map_err(|_| Error::InstantiationFailed)?;
+
+            let account = ink_lang::ToAccountId::to_account_id(&game);
+
+            self.set_game(board, account);
+
+            Ok(account)
+        }
+
+        /// Records result of the finished game on the `board`
+        ///
+        /// Aborted or voided game is not scored, the board is freed so a new game can be
+        /// created for it
+        #[ink(message)]
+        pub fn report_result(&mut self, board: u32) -> Result<()> {
+            let entry = self.boards.get(board).ok_or(Error::InvalidBoard)?;
+
+            if entry.outcome.is_some() {
+                return Err(Error::ResultAlreadyRecorded);
+            }
+
+            let game = entry.game.ok_or(Error::GameNotCreated)?;
+            let contract: DotChess = ink_env::call::FromAccountId::from_account_id(game);
+
+            match Self::outcome(contract.status())? {
+                Some(outcome) => self.record_result(board, outcome),
+                None => {
+                    self.reset_board(board);
+
+                    Ok(())
+                }
+            }
+        }
+
+        /// Ends the tournament after the last round and distributes the prize pool
+        #[ink(message)]
+        pub fn finish(&mut self) -> Result<()> {
+            match *self.phase {
+                Phase::Registration => return Err(Error::TournamentNotStarted),
+                Phase::Finished => return Err(Error::TournamentAlreadyOver),
+                Phase::Running { round } => {
+                    if round + 1 < self.rounds() {
+                        return Err(Error::RoundNotComplete);
+                    }
+
+                    if !self.round_complete(round) {
+                        return Err(Error::RoundNotComplete);
+                    }
+                }
+            }
+
+            *self.phase = Phase::Finished;
+
+            let pool = self.prize_pool();
+            let mut paid = 0;
+
+            for (place, (player, _)) in self.standings().into_iter().enumerate() {
+                let share = match self.prizes.get(place as u32) {
+                    Some(share) => *share as Balance,
+                    None => break,
+                };
+
+                let amount = pool * share / 100;
+
+                if amount > 0 {
+                    self.env().transfer(player, amount)?;
+                    self.env().emit_event(PrizePaid {
+                        player,
+                        place: place as u32,
+                        amount,
+                    });
+                }
+
+                paid += amount;
+            }
+
+            if pool > paid {
+                self.env().transfer(self.organizer, pool - paid)?;
+            }
+
+            Ok(())
+        }
+
+        /// Current phase
+        #[ink(message)]
+        pub fn phase(&self) -> Phase {
+            *self.phase
+        }
+
+        /// Total number of rounds
+        #[ink(message)]
+        pub fn rounds(&self) -> u32 {
+            match self.config.format {
+                Format::RoundRobin => pairing::round_robin_rounds(self.entrants.len()),
+                Format::Swiss { rounds } => rounds,
+            }
+        }
+
+        /// Registered entrants in registration order
+        #[ink(message)]
+        pub fn entrants(&self) -> alloc::vec::Vec<AccountId> {
+            self.entrants.iter().copied().collect()
+        }
+
+        /// Sum of all entry fees
+        #[ink(message)]
+        pub fn prize_pool(&self) -> Balance {
+            self.config.entry_fee * self.entrants.len() as Balance
+        }
+
+        /// Boards of the `round`th round
+        #[ink(message)]
+        pub fn boards(&self, round: u32) -> alloc::vec::Vec<Board> {
+            self.boards
+                .iter()
+                .filter(|board| board.round == round)
+                .copied()
+                .collect()
+        }
+
+        /// Entrants sorted by points and tie-breaks from recorded results
+        #[ink(message)]
+        pub fn standings(&self) -> alloc::vec::Vec<(AccountId, Standing)> {
+            pairing::standings(self.entrants.len(), &self.played())
+                .into_iter()
+                .map(|standing| (self.entrant(standing.player), standing))
+                .collect()
+        }
+
+        /// Returns outcome of a game with `status`, none if the game did not count
+        fn outcome(status: Status) -> Result<Option<Outcome>> {
+            match status {
+                Status::Finished {
+                    reason: GameOverReason::Aborted,
+                    ..
+                }
+                | Status::Finished {
+                    reason: GameOverReason::Void,
+                    ..
+                } => Ok(None),
+                Status::Finished {
+                    winner: Some(Side::White),
+                    ..
+                } => Ok(Some(Outcome::WhiteWins)),
+                Status::Finished {
+                    winner: Some(Side::Black),
+                    ..
+                } => Ok(Some(Outcome::BlackWins)),
+                Status::Finished { winner: None, .. } => Ok(Some(Outcome::Draw)),
+                _ => Err(Error::GameNotOver),
+            }
+        }
+
+        fn record_result(&mut self, board: u32, outcome: Outcome) -> Result<()> {
+            let entry = self.boards.get_mut(board).ok_or(Error::InvalidBoard)?;
+
+            if entry.outcome.is_some() {
+                return Err(Error::ResultAlreadyRecorded);
+            }
+
+            entry.outcome = Some(outcome);
+            self.env().emit_event(ResultRecorded { board, outcome });
+
+            Ok(())
+        }
+
+        fn pending_board(&self, board: u32) -> Result<Board> {
+            let round = match *self.phase {
+                Phase::Running { round } => round,
+                Phase::Registration => return Err(Error::TournamentNotStarted),
+                Phase::Finished => return Err(Error::TournamentAlreadyOver),
+            };
+
+            let entry = *self.boards.get(board).ok_or(Error::InvalidBoard)?;
+
+            if entry.round != round {
+                return Err(Error::InvalidBoard);
+            }
+
+            if entry.game.is_some() || entry.outcome.is_some() {
+                return Err(Error::GameAlreadyCreated);
+            }
+
+            Ok(entry)
+        }
+
+        fn set_game(&mut self, board: u32, game: AccountId) {
+            if let Some(entry) = self.boards.get_mut(board) {
+                entry.game = Some(game);
+            }
+
+            self.env().emit_event(GameRegistered { board, game });
+        }
+
+        fn reset_board(&mut self, board: u32) {
+            if let Some(entry) = self.boards.get_mut(board) {
+                entry.game = None;
+            }
+        }
+
+        fn round_complete(&self, round: u32) -> bool {
+            let start = match self.round_start.get(round) {
+                Some(start) => *start,
+                None => return false,
+            };
+
+            self.boards
+                .iter()
+                .skip(start as usize)
+                .take_while(|board| board.round == round)
+                .all(|board| board.outcome.is_some())
+        }
+
+        fn played(&self) -> alloc::vec::Vec<Played> {
+            self.boards
+                .iter()
+                .filter_map(|board| {
+                    board.outcome.map(|outcome| Played {
+                        white: board.white,
+                        black: board.black,
+                        outcome,
+                    })
+                })
+                .collect()
+        }
+
+        fn entrant(&self, player: u32) -> AccountId {
+            *self.entrants.get(player).unwrap()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_lang as ink;
+
+        fn accounts() -> ink_env::test::DefaultAccounts<ink_env::DefaultEnvironment> {
+            ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().unwrap()
+        }
+
+        fn set_caller(caller: AccountId, transferred: Balance) {
+            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
+                caller,
+                ink_env::account_id::<ink_env::DefaultEnvironment>().unwrap(),
+                1000000,
+                transferred,
+                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])),
+            );
+        }
+
+        fn tournament(format: Format, entry_fee: Balance) -> Tournament {
+            Tournament::new(
+                format,
+                entry_fee,
+                8,
+                Hash::from([0x42; 32]),
+                TimeControl::Fischer {
+                    base: 100,
+                    increment: 1,
+                },
+                ClockUnit::Blocks,
+                alloc::vec![70, 30],
+            )
+        }
+
+        #[ink::test]
+        fn registration_requires_entry_fee() {
+            let accounts = accounts();
+            let mut tournament = tournament(Format::RoundRobin, 10);
+
+            set_caller(accounts.bob, 5);
+            assert_eq!(tournament.register(), Err(Error::InvalidEntryFee));
+
+            set_caller(accounts.bob, 10);
+            assert_eq!(tournament.register(), Ok(()));
+            assert_eq!(tournament.register(), Err(Error::AlreadyRegistered));
+
+            assert_eq!(tournament.entrants(), [accounts.bob]);
+            assert_eq!(tournament.prize_pool(), 10);
+        }
+
+        #[ink::test]
+        fn round_robin_tournament() {
+            let accounts = accounts();
+            let mut tournament = tournament(Format::RoundRobin, 0);
+
+            for player in &[accounts.alice, accounts.bob, accounts.charlie] {
+                set_caller(*player, 0);
+                tournament.register().unwrap();
+            }
+
+            set_caller(accounts.bob, 0);
+            assert_eq!(tournament.start_round(), Err(Error::InvalidCaller));
+
+            set_caller(accounts.alice, 0);
+            assert_eq!(tournament.start_round(), Ok(()));
+            assert_eq!(tournament.rounds(), 3);
+            assert_eq!(tournament.phase(), Phase::Running { round: 0 });
+            assert_eq!(tournament.start_round(), Err(Error::RoundNotComplete));
+
+            for round in 0..3 {
+                if round > 0 {
+                    tournament.start_round().unwrap();
+                }
+
+                for board in tournament.boards(round) {
+                    if board.outcome.is_none() {
+                        let index = tournament
+                            .boards
+                            .iter()
+                            .position(|entry| *entry == board)
+                            .unwrap() as u32;
+
+                        // Lower entrant index always wins
+                        let outcome = if board.white < board.black.unwrap() {
+                            Outcome::WhiteWins
+                        } else {
+                            Outcome::BlackWins
+                        };
+
+                        tournament.record_result(index, outcome).unwrap();
+                    }
+                }
+            }
+
+            assert_eq!(tournament.start_round(), Err(Error::NoRoundsLeft));
+            assert_eq!(tournament.finish(), Ok(()));
+            assert_eq!(tournament.phase(), Phase::Finished);
+
+            let standings = tournament.standings();
+
+            assert_eq!(standings[0].0, accounts.alice);
+            assert_eq!(standings[0].1.points, 6);
+            assert_eq!(standings[1].0, accounts.bob);
+            assert_eq!(standings[2].0, accounts.charlie);
+        }
+
+        #[ink::test]
+        fn swiss_round_needs_results() {
+            let accounts = accounts();
+            let mut tournament = tournament(Format::Swiss { rounds: 2 }, 0);
+
+            for player in &[
+                accounts.alice,
+                accounts.bob,
+                accounts.charlie,
+                accounts.django,
+            ] {
+                set_caller(*player, 0);
+                tournament.register().unwrap();
+            }
+
+            set_caller(accounts.alice, 0);
+            tournament.start_round().unwrap();
+
+            assert_eq!(tournament.boards(0).len(), 2);
+            assert_eq!(tournament.finish(), Err(Error::RoundNotComplete));
+
+            tournament.record_result(0, Outcome::WhiteWins).unwrap();
+            assert_eq!(
+                tournament.record_result(0, Outcome::Draw),
+                Err(Error::ResultAlreadyRecorded)
+            );
+            tournament.record_result(1, Outcome::BlackWins).unwrap();
+
+            tournament.start_round().unwrap();
+
+            let boards = tournament.boards(1);
+
+            // Winners alice and django meet in the second round
+            assert_eq!((boards[0].white, boards[0].black), (3, Some(0)));
+            assert_eq!(tournament.create_game(0, [0; 4]), Err(Error::InvalidBoard));
+        }
+
+        #[ink::test]
+        fn aborted_and_voided_games_are_not_scored() {
+            let accounts = accounts();
+            let mut tournament = tournament(Format::Swiss { rounds: 1 }, 0);
+
+            for player in &[accounts.alice, accounts.bob] {
+                set_caller(*player, 0);
+                tournament.register().unwrap();
+            }
+
+            set_caller(accounts.alice, 0);
+            tournament.start_round().unwrap();
+            tournament.set_game(0, AccountId::from([0x10; 32]));
+
+            let aborted = Status::Finished {
+                winner: None,
+                reason: GameOverReason::Aborted,
+            };
+            let drawn = Status::Finished {
+                winner: None,
+                reason: GameOverReason::DrawAgreement,
+            };
+
+            let voided = Status::Finished {
+                winner: None,
+                reason: GameOverReason::Void,
+            };
+
+            assert_eq!(Tournament::outcome(aborted), Ok(None));
+            assert_eq!(Tournament::outcome(voided), Ok(None));
+            assert_eq!(Tournament::outcome(drawn), Ok(Some(Outcome::Draw)));
+            assert_eq!(Tournament::outcome(Status::Active), Err(Error::GameNotOver));
+
+            tournament.reset_board(0);
+
+            assert_eq!(tournament.boards(0)[0].game, None);
+            assert_eq!(tournament.pending_board(0).map(|board| board.white), Ok(0));
+            assert_eq!(tournament.finish(), Err(Error::RoundNotComplete));
+        }
+    }
+}
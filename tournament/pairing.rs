@@ -0,0 +1,452 @@
+//! Pairing and standings computation
+//!
+//! Players are identified by their registration index. Points are counted in
+//! half points so that draws do not need fractions.
+
+use alloc::vec::Vec;
+use ink_storage::traits::{PackedLayout, SpreadLayout, StorageLayout};
+use scale::{Decode, Encode};
+
+pub type Player = u32;
+
+/// Upper bound on partial pairings tried while avoiding rematches
+const MAX_PAIRING_STEPS: u32 = 1_000;
+
+/// Result of a tournament game
+#[derive(Copy, Clone, Encode, Decode, SpreadLayout, PackedLayout)]
+#[cfg_attr(
+    feature = "std",
+    derive(Debug, PartialEq, Eq, scale_info::TypeInfo, StorageLayout)
+)]
+pub enum Outcome {
+    WhiteWins,
+    BlackWins,
+    Draw,
+}
+
+/// Pairing of two players, `black` is none when `white` gets a bye
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq))]
+pub struct Pairing {
+    pub white: Player,
+    pub black: Option<Player>,
+}
+
+/// Finished game between `white` and `black`, `black` is none for a bye
+#[derive(Copy, Clone)]
+pub struct Played {
+    pub white: Player,
+    pub black: Option<Player>,
+    pub outcome: Outcome,
+}
+
+/// Standing of a player, `points` and `buchholz` are in half points,
+/// `sonneborn_berger` is in quarter points
+#[derive(Copy, Clone, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq, scale_info::TypeInfo))]
+pub struct Standing {
+    pub player: Player,
+    pub points: u32,
+    pub buchholz: u32,
+    pub sonneborn_berger: u32,
+}
+
+/// Returns number of rounds of a round robin tournament of `players` players
+pub fn round_robin_rounds(players: u32) -> u32 {
+    if players % 2 == 0 {
+        players.saturating_sub(1)
+    } else {
+        players
+    }
+}
+
+/// Returns pairings of the `round`th (zero based) round of a round robin
+/// tournament of `players` players, pairings are generated by the circle method
+pub fn round_robin(players: u32, round: u32) -> Vec<Pairing> {
+    // Odd number of players is completed by a dummy, whoever meets it gets a bye
+    let n = players + players % 2;
+    let rotating = n - 1;
+    let round = round % rotating;
+
+    let mut pairings = Vec::new();
+
+    for i in 0..n / 2 {
+        let (a, b) = if i == 0 {
+            // Fixed player alternates colors
+            if round % 2 == 0 {
+                (round, n - 1)
+            } else {
+                (n - 1, round)
+            }
+        } else {
+            ((round + i) % rotating, (round + rotating - i) % rotating)
+        };
+
+        if a >= players {
+            pairings.push(Pairing {
+                white: b,
+                black: None,
+            });
+        } else if b >= players {
+            pairings.push(Pairing {
+                white: a,
+                black: None,
+            });
+        } else {
+            pairings.push(Pairing {
+                white: a,
+                black: Some(b),
+            });
+        }
+    }
+
+    pairings
+}
+
+/// Returns pairings of the next Swiss round given games `played` so far
+///
+/// Players are ranked by points and registration order. Each player is paired with
+/// the highest ranked player they have not met yet, the lowest ranked player
+/// without a bye gets one if the number of players is odd. If no pairing without rematches
+/// is found within `MAX_PAIRING_STEPS`, players are paired in ranking order.
+pub fn swiss(players: u32, played: &[Played]) -> Vec<Pairing> {
+    let points = points(players, played);
+
+    let mut ranking: Vec<Player> = (0..players).collect();
+    ranking.sort_by(|a, b| points[*b as usize].cmp(&points[*a as usize]));
+
+    let mut pairings = Vec::new();
+
+    if players % 2 == 1 {
+        let had_bye = |player: Player| {
+            played
+                .iter()
+                .any(|game| game.white == player && game.black.is_none())
+        };
+
+        let index = ranking
+            .iter()
+            .rposition(|player| !had_bye(*player))
+            .unwrap_or(ranking.len() - 1);
+
+        pairings.push(Pairing {
+            white: ranking.remove(index),
+            black: None,
+        });
+    }
+
+    let met = |a: Player, b: Player| {
+        played.iter().any(|game| {
+            (game.white == a && game.black == Some(b)) || (game.white == b && game.black == Some(a))
+        })
+    };
+
+    let mut pairs = Vec::new();
+    let mut steps = MAX_PAIRING_STEPS;
+
+    // Avoid rematches if possible, fall back to pairing in ranking order
+    if !pair_unmet(&ranking, &mut pairs, &met, &mut steps) {
+        pairs = ranking.chunks(2).map(|pair| (pair[0], pair[1])).collect();
+    }
+
+    for (a, b) in pairs {
+        let (white, black) = if color_balance(a, played) <= color_balance(b, played) {
+            (a, b)
+        } else {
+            (b, a)
+        };
+
+        pairings.push(Pairing {
+            white,
+            black: Some(black),
+        });
+    }
+
+    pairings
+}
+
+fn pair_unmet<F>(
+    remaining: &[Player],
+    pairs: &mut Vec<(Player, Player)>,
+    met: &F,
+    steps: &mut u32,
+) -> bool
+where
+    F: Fn(Player, Player) -> bool,
+{
+    if remaining.is_empty() {
+        return true;
+    }
+
+    if *steps == 0 {
+        return false;
+    }
+
+    *steps -= 1;
+
+    let first = remaining[0];
+
+    for &second in &remaining[1..] {
+        if met(first, second) {
+            continue;
+        }
+
+        let rest: Vec<Player> = remaining[1..]
+            .iter()
+            .copied()
+            .filter(|player| *player != second)
+            .collect();
+
+        pairs.push((first, second));
+
+        if pair_unmet(&rest, pairs, met, steps) {
+            return true;
+        }
+
+        pairs.pop();
+    }
+
+    false
+}
+
+/// Returns number of games played with white minus number of games played with black
+fn color_balance(player: Player, played: &[Played]) -> i32 {
+    played
+        .iter()
+        .filter(|game| game.black.is_some())
+        .map(|game| {
+            if game.white == player {
+                1
+            } else if game.black == Some(player) {
+                -1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+fn points(players: u32, played: &[Played]) -> Vec<u32> {
+    let mut points = alloc::vec![0; players as usize];
+
+    for game in played {
+        let (white, black) = match game.outcome {
+            Outcome::WhiteWins => (2, 0),
+            Outcome::BlackWins => (0, 2),
+            Outcome::Draw => (1, 1),
+        };
+
+        points[game.white as usize] += white;
+
+        if let Some(player) = game.black {
+            points[player as usize] += black;
+        }
+    }
+
+    points
+}
+
+/// Returns standings of `players` players sorted by points, Buchholz
+/// and Sonneborn-Berger score, byes do not count towards tie-breaks
+pub fn standings(players: u32, played: &[Played]) -> Vec<Standing> {
+    let points = points(players, played);
+
+    let mut standings: Vec<Standing> = (0..players)
+        .map(|player| Standing {
+            player,
+            points: points[player as usize],
+            buchholz: 0,
+            sonneborn_berger: 0,
+        })
+        .collect();
+
+    for game in played {
+        let black = match game.black {
+            Some(black) => black,
+            None => continue,
+        };
+
+        let white = game.white;
+        let white_points = points[white as usize];
+        let black_points = points[black as usize];
+
+        standings[white as usize].buchholz += black_points;
+        standings[black as usize].buchholz += white_points;
+
+        match game.outcome {
+            Outcome::WhiteWins => standings[white as usize].sonneborn_berger += 2 * black_points,
+            Outcome::BlackWins => standings[black as usize].sonneborn_berger += 2 * white_points,
+            Outcome::Draw => {
+                standings[white as usize].sonneborn_berger += black_points;
+                standings[black as usize].sonneborn_berger += white_points;
+            }
+        }
+    }
+
+    standings.sort_by(|a, b| {
+        b.points
+            .cmp(&a.points)
+            .then(b.buchholz.cmp(&a.buchholz))
+            .then(b.sonneborn_berger.cmp(&a.sonneborn_berger))
+            .then(a.player.cmp(&b.player))
+    });
+
+    standings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(pairings: &[Pairing]) -> Vec<(Player, Option<Player>)> {
+        pairings.iter().map(|p| (p.white, p.black)).collect()
+    }
+
+    #[test]
+    fn round_robin_everyone_meets_once() {
+        let players = 6;
+        let mut met = Vec::new();
+
+        for round in 0..round_robin_rounds(players) {
+            for pairing in round_robin(players, round) {
+                let black = pairing.black.unwrap();
+                let pair = (pairing.white.min(black), pairing.white.max(black));
+
+                assert!(!met.contains(&pair));
+                met.push(pair);
+            }
+        }
+
+        assert_eq!(met.len(), 15);
+    }
+
+    #[test]
+    fn round_robin_odd_players_get_byes() {
+        let players = 3;
+        let mut byes = Vec::new();
+
+        for round in 0..round_robin_rounds(players) {
+            let pairings = round_robin(players, round);
+
+            assert_eq!(pairings.len(), 2);
+            byes.extend(
+                pairings
+                    .iter()
+                    .filter(|p| p.black.is_none())
+                    .map(|p| p.white),
+            );
+        }
+
+        byes.sort();
+        assert_eq!(byes, [0, 1, 2]);
+    }
+
+    #[test]
+    fn swiss_pairs_by_points_without_rematches() {
+        let first = swiss(4, &[]);
+        assert_eq!(pairs(&first), [(0, Some(1)), (2, Some(3))]);
+
+        let played = [
+            Played {
+                white: 0,
+                black: Some(1),
+                outcome: Outcome::WhiteWins,
+            },
+            Played {
+                white: 2,
+                black: Some(3),
+                outcome: Outcome::BlackWins,
+            },
+        ];
+
+        // Winners 0 and 3 meet, 0 had white so 3 gets it
+        assert_eq!(pairs(&swiss(4, &played)), [(3, Some(0)), (1, Some(2))]);
+    }
+
+    #[test]
+    fn swiss_pairing_search_is_bounded() {
+        // Last ranked player has lost to everyone, so no pairing avoids rematches
+        let played: Vec<Played> = (0..19)
+            .map(|player| Played {
+                white: player,
+                black: Some(19),
+                outcome: Outcome::WhiteWins,
+            })
+            .collect();
+
+        let pairings = swiss(20, &played);
+
+        assert_eq!(pairings.len(), 10);
+        assert_eq!(pairs(&pairings)[0], (0, Some(1)));
+        assert_eq!(pairs(&pairings)[9], (19, Some(18)));
+    }
+
+    #[test]
+    fn swiss_bye_goes_to_lowest_player_without_bye() {
+        let played = [
+            Played {
+                white: 0,
+                black: Some(1),
+                outcome: Outcome::WhiteWins,
+            },
+            Played {
+                white: 2,
+                black: None,
+                outcome: Outcome::WhiteWins,
+            },
+        ];
+
+        let pairings = swiss(3, &played);
+
+        assert_eq!(
+            pairings[0],
+            Pairing {
+                white: 1,
+                black: None
+            }
+        );
+    }
+
+    #[test]
+    fn standings_tie_breaks() {
+        let played = [
+            Played {
+                white: 0,
+                black: Some(1),
+                outcome: Outcome::WhiteWins,
+            },
+            Played {
+                white: 2,
+                black: Some(3),
+                outcome: Outcome::Draw,
+            },
+            Played {
+                white: 1,
+                black: Some(2),
+                outcome: Outcome::WhiteWins,
+            },
+            Played {
+                white: 3,
+                black: Some(0),
+                outcome: Outcome::Draw,
+            },
+        ];
+
+        let standings = standings(4, &played);
+
+        assert_eq!(
+            standings[0],
+            Standing {
+                player: 0,
+                points: 3,
+                buchholz: 4,
+                sonneborn_berger: 6,
+            }
+        );
+
+        // Equal points and Buchholz, player 3 drew with the leader
+        assert_eq!(standings[1].player, 3);
+        assert_eq!(standings[2].player, 1);
+        assert_eq!(standings[3].player, 2);
+    }
+}